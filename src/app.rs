@@ -1,4 +1,8 @@
-use std::{collections::VecDeque, env, io, thread, time::Duration};
+use std::{
+    collections::{HashMap, VecDeque},
+    env, io, thread,
+    time::Duration,
+};
 
 use crossbeam::{
     channel::{unbounded, Receiver},
@@ -6,20 +10,36 @@ use crossbeam::{
 };
 use crossterm::event::{self, Event, KeyCode};
 use reqwest::Client;
-use serde_json::Value;
 use tui::{backend::Backend, widgets::TableState, Terminal};
 use tui_logger::{TuiWidgetEvent, TuiWidgetState};
 
 use crate::{
     active_player::{self, AbilityRanks},
-    champions::{self, ActiveChampion},
-    dmg, network, ui,
-    utils::{deserializer, resistance, teams},
+    champions::{self, definition::ChampionDefinition, Champion},
+    ddragon, dmg,
+    loadout::{CritMode, Loadout},
+    network, optimizer,
+    rotation::NamedRotation,
+    stacks::Stacks,
+    ui,
+    utils::{deserializer, health, resistance, shields, teams},
 };
 
 pub struct App {
     pub burst_table_state: TableState,
     pub burst_table_items: Vec<Vec<String>>,
+    pub burst_table_killable: Vec<bool>,
+    pub champion_name: String,
+    pub champion_supported: bool,
+    // Set when the champion's definition was written for a different patch than the game's
+    pub patch_warning: Option<String>,
+    // Game time of the last tick, used to tell when a new game has started
+    pub last_game_time: f64,
+    pub stacks: Stacks,
+    // Set when the stacks only grant stats, so the count doesn't change the damage
+    pub stacks_display_only: bool,
+    // Stacks typed in by the user while the stack prompt is open
+    pub stack_input: Option<String>,
     pub logger_state: TuiWidgetState,
     pub draw_logger: bool,
     pub logger_scroll_mode: bool,
@@ -36,17 +56,31 @@ pub struct App {
     pub vs_per_min: String,
     pub vs_per_min_vecdeque: VecDeque<(f64, f64)>,
     pub vs_per_min_dataset: Vec<(f64, f64)>,
+    pub rotations: Vec<NamedRotation>,
+    pub trade_windows: Vec<f64>,
+    pub teamfight_targets: usize,
+    pub crit_mode: CritMode,
     pub use_sample_data: bool,
+    pub champion_definitions: HashMap<String, ChampionDefinition>,
+    pub ddragon_version: String,
+    pub ddragon_url: String,
+    pub ddragon_cache_dir: String,
+    pub ddragon_bundled_dir: String,
+    pub ddragon_sample_dir: String,
     pub active_player_json_url: String,
     pub active_player_json_sample: String,
     pub all_players_json_url: String,
     pub all_players_json_sample: String,
     pub game_stats_url: String,
+    pub all_game_data_url: String,
     pub game_stats_json_sample: String,
 }
 
 impl App {
-    pub fn new() -> App {
+    pub fn new(
+        rotations: Vec<NamedRotation>,
+        champion_definitions: HashMap<String, ChampionDefinition>,
+    ) -> App {
         let dataset_length = get_dataset_length();
         App {
             burst_table_state: TableState::default(),
@@ -77,6 +111,14 @@ impl App {
                     "Row53".to_string(),
                 ],
             ],
+            burst_table_killable: vec![false; 5],
+            champion_name: String::new(),
+            champion_supported: false,
+            patch_warning: None,
+            last_game_time: 0.0,
+            stacks: Stacks::default(),
+            stacks_display_only: false,
+            stack_input: None,
             logger_state: TuiWidgetState::default(),
             draw_logger: false,
             logger_scroll_mode: false,
@@ -93,16 +135,58 @@ impl App {
             vs_per_min: "42".to_string(),
             vs_per_min_vecdeque: VecDeque::from(vec![(0.0, 0.0); dataset_length]),
             vs_per_min_dataset: vec![(0.0, 0.0); dataset_length],
+            rotations,
+            trade_windows: env::var("TRADE_WINDOWS")
+                .unwrap_or("3,10".to_string())
+                .split(',')
+                .map(|w| {
+                    w.trim()
+                        .parse()
+                        .expect("TRADE_WINDOWS is not a list of numbers")
+                })
+                .collect(),
+            teamfight_targets: env::var("TEAMFIGHT_TARGETS")
+                .unwrap_or("3".to_string())
+                .parse()
+                .expect("TEAMFIGHT_TARGETS is not a number"),
+            crit_mode: env::var("CRIT_MODE")
+                .unwrap_or("expected".to_string())
+                .parse()
+                .unwrap(),
             use_sample_data: env::var("USE_SAMPLE_DATA").unwrap_or("false".to_string()) == "true",
+            champion_definitions,
+            ddragon_version: env::var("DDRAGON_VERSION")
+                .unwrap_or(ddragon::AUTO_VERSION.to_string()),
+            ddragon_url: env::var("DDRAGON_URL")
+                .unwrap_or("http://ddragon.leagueoflegends.com".to_string()),
+            ddragon_cache_dir: env::var("DDRAGON_CACHE").unwrap_or("./cache/ddragon".to_string()),
+            ddragon_bundled_dir: env::var("DDRAGON_BUNDLED")
+                .unwrap_or("./resources/ddragon/12.13.1".to_string()),
+            ddragon_sample_dir: env::var("DDRAGON_SAMPLE")
+                .unwrap_or("./resources/fixtures/ddragon/12.13.1".to_string()),
             active_player_json_url: env::var("ACTIVE_PLAYER_URL").unwrap(),
             active_player_json_sample: env::var("ACTIVE_PLAYER_JSON_SAMPLE").unwrap(),
             all_players_json_url: env::var("ALL_PLAYERS_URL").unwrap(),
             all_players_json_sample: env::var("ALL_PLAYERS_JSON_SAMPLE").unwrap(),
             game_stats_url: env::var("GAME_STATS_URL").unwrap(),
+            all_game_data_url: env::var("ALL_GAME_DATA_URL").unwrap(),
             game_stats_json_sample: env::var("GAME_STATS_JSON_SAMPLE").unwrap(),
         }
     }
 
+    // Header of the burst table, with one burst column per rotation
+    pub fn burst_table_header(&self) -> Vec<String> {
+        let mut header = vec!["Champion".to_string(), "Level".to_string()];
+        header.extend(self.rotations.iter().map(|r| r.name.clone()));
+        header.push(format!("TF x{}", self.teamfight_targets));
+        header.extend(self.trade_windows.iter().map(|w| format!("{}s", w)));
+        header.push("combo".to_string());
+        header.push("%HP".to_string());
+        header.push("%EHP".to_string());
+        header.push("Exec".to_string());
+        header
+    }
+
     fn on_tick(&mut self, game_time: f64) {
         self.gold_per_min_vecdeque.pop_front();
         self.gold_per_min_vecdeque
@@ -126,18 +210,30 @@ pub async fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io
         warn!("use_sample_data is true, using JSON files in resources directory");
     }
 
-    let ddragon_url = "http://ddragon.leagueoflegends.com/cdn/12.13.1/data/en_US/champion.json";
-
-    let ddragon_data: Value = serde_json::from_str(
-        &network::request(&client, ddragon_url)
-            .await
-            .text()
-            .await
-            .expect("Failed to parse data for String"),
-    )
-    .expect("Failed to deserialize String into JSON Value");
+    // Sample data runs don't need a connection, so they only use what's already on disk and
+    // the snapshot trimmed to the sample players
+    let ddragon_cache = ddragon::Cache::new(
+        &app.ddragon_cache_dir,
+        if app.use_sample_data {
+            &app.ddragon_sample_dir
+        } else {
+            &app.ddragon_bundled_dir
+        },
+        &app.ddragon_url,
+        !app.use_sample_data,
+    );
+    let ddragon_versions = if app.ddragon_version == ddragon::AUTO_VERSION {
+        ddragon_cache.versions(&client).await
+    } else {
+        Vec::new()
+    };
+    // Data Dragon is loaded once the game's patch is known, along with the version asked for
+    let mut loaded_ddragon: Option<(Option<String>, ddragon::DataDragon)> = None;
+    // Version of the game client, asked for again whenever a new game starts and on every
+    // tick until the client reports it
+    let mut client_version: Option<String> = None;
 
-    let champion = champions::match_champion("Orianna");
+    let mut champion: Option<Box<dyn Champion>> = None;
 
     let mut cycle: usize = 0;
 
@@ -146,7 +242,6 @@ pub async fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io
 
     // Applicaiton loop
     loop {
-
         if app.use_sample_data {
             debug!("cycle: {}", cycle);
             if cycle
@@ -163,7 +258,6 @@ pub async fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io
         let (active_player_data, all_player_data, game_data) =
             deserializer::deserializer(&app, &client, cycle).await;
 
-            
         if cycle == 0 {
             let offset = env::var("SAMPLE_RATE").unwrap().parse::<usize>().unwrap() / 1000;
             let offset_vec = || -> Vec<(f64, f64)> {
@@ -174,7 +268,7 @@ pub async fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io
                 x.into_iter().rev().collect()
             };
             app.gold_per_min_vecdeque = VecDeque::from(offset_vec());
-                // app.gold_per_min_dataset[ele.0] = ((game_data.game_time - (env::var("SAMPLE_RATE").unwrap().parse::<f64>().unwrap() / 1000.0) * (app.gold_per_min_vecdeque.len() - ele.0) as f64), 0.0);
+            // app.gold_per_min_dataset[ele.0] = ((game_data.game_time - (env::var("SAMPLE_RATE").unwrap().parse::<f64>().unwrap() / 1000.0) * (app.gold_per_min_vecdeque.len() - ele.0) as f64), 0.0);
             app.gold_per_min_dataset = vec![(0.0, 0.0); get_dataset_length()];
             app.cs_per_min_vecdeque = VecDeque::from(offset_vec());
             app.cs_per_min_dataset = vec![(0.0, 0.0); get_dataset_length()];
@@ -184,10 +278,85 @@ pub async fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io
 
         debug!("game_time: {}", game_data.game_time);
 
+        // Resolve the played champion again when a new game has started, seen from the
+        // champion changing or the game clock going back
+        let champion_name = teams::active_champion_name(&active_player_data, &all_player_data);
+        let new_game =
+            champion_name != app.champion_name || game_data.game_time < app.last_game_time;
+        app.last_game_time = game_data.game_time;
+        if new_game {
+            champion = champions::match_champion(&champion_name, &app.champion_definitions);
+            match &champion {
+                Some(c) => info!("Calculating burst for {}", c.name()),
+                None => warn!("Champion {} is not supported", champion_name),
+            }
+            app.champion_supported = champion.is_some();
+            app.champion_name = champion_name;
+            app.stacks = Stacks::new(champion.as_ref().and_then(|c| c.stack_source()));
+            app.stacks_display_only = champion.as_ref().is_some_and(|c| !c.scales_with_stacks());
+            client_version = None;
+        }
+        if client_version.is_none() {
+            client_version = deserializer::game_version(&app, &client).await;
+        }
+
+        let version = ddragon::resolve_version(
+            &app.ddragon_version,
+            client_version.as_deref(),
+            &ddragon_versions,
+        );
+        if loaded_ddragon.as_ref().map(|(requested, _)| requested) != Some(&version) {
+            // Without any Data Dragon every opponant is shown as missing until the version
+            // changes and loading is tried again
+            let data = ddragon_cache
+                .load(&client, version.as_deref())
+                .await
+                .unwrap_or_else(|err| {
+                    error!("{}", err);
+                    ddragon::DataDragon::default()
+                });
+            loaded_ddragon = Some((version, data));
+        }
+        let ddragon = &loaded_ddragon.as_ref().unwrap().1;
+        let (ddragon_data, ddragon_items) = (&ddragon.champions, &ddragon.items);
+
+        let patch_warning = champion.as_ref().and_then(|c| {
+            let defined = ddragon::patch(c.definition().patch.as_deref()?)?;
+            let game = ddragon::game_patch(client_version.as_deref(), &ddragon.version)?;
+            (defined != game).then(|| {
+                format!(
+                    "{} is defined for patch {}, the game is on {}",
+                    c.name(),
+                    defined,
+                    game
+                )
+            })
+        });
+        if patch_warning != app.patch_warning {
+            if let Some(warning) = &patch_warning {
+                warn!("{}", warning);
+            }
+            app.patch_warning = patch_warning;
+        }
+
+        if let Some(champion) = champion.as_mut() {
+            app.stacks.update(
+                &active_player_data,
+                champion.definition(),
+                &teams::active_all_player(&active_player_data, &all_player_data).items,
+                ddragon_items,
+            );
+            champion.set_stacks(app.stacks.count);
+        }
+
         let opponant_team = teams::OpponantTeam::new(&active_player_data, &all_player_data);
 
-        let resistance =
-            resistance::Resistance::new(&active_player_data, &all_player_data, &ddragon_data);
+        let resistance = resistance::Resistance::new(
+            &active_player_data,
+            &all_player_data,
+            ddragon_data,
+            ddragon_items,
+        );
 
         // TODO: Find a better place for this
         let ability_ranks = AbilityRanks::new(
@@ -197,12 +366,25 @@ pub async fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io
             active_player_data.abilities.r.ability_level,
         );
 
-        app.burst_table_items = build_enemy_team_display_data(
-            &champion,
+        let loadout = Loadout::new(&active_player_data, &all_player_data, app.crit_mode);
+
+        let health = health::Health::new(
+            &active_player_data,
+            &all_player_data,
+            ddragon_data,
+            ddragon_items,
+        );
+
+        let shields = shields::Shields::new(&active_player_data, &all_player_data, ddragon_items);
+
+        (app.burst_table_items, app.burst_table_killable) = build_enemy_team_display_data(
+            champion.as_deref(),
             &active_player_data,
             ability_ranks,
+            &app,
             opponant_team,
-            resistance,
+            &loadout,
+            build_targets(resistance, health, shields),
         );
 
         app.gold_total = get_total_gold_earned(
@@ -217,7 +399,7 @@ pub async fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io
             if i.summoner_name == active_player_data.summoner_name {
                 app.cs_total = i.scores.creep_score as f64;
                 app.cs_per_min = format!("{:.1}", get_per_min(app.cs_total, game_data.game_time));
-                app.vs_total = i.scores.ward_score as f64;
+                app.vs_total = i.scores.ward_score;
                 app.vs_per_min = format!("{:.1}", get_per_min(app.vs_total, game_data.game_time));
             }
         }
@@ -233,12 +415,22 @@ pub async fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io
                     match event.unwrap() {
                         Event::Key(key_event) => {
                             match key_event.code {
+                                // The stack prompt takes every key while it's open
+                                code if app.stack_input.is_some() => {
+                                    let entered = edit_stack_input(&mut app, code);
+                                    if entered {
+                                        break;
+                                    }
+                                }
                                 KeyCode::Char('q') => {
                                     return Ok(());
                                 }
                                 KeyCode::Char('s') => {
                                     break;
                                 }
+                                KeyCode::Char('k') if app.stacks.source.is_some() => {
+                                    app.stack_input = Some(String::new());
+                                }
                                 KeyCode::Char('l') => {
                                     info!("Toggling logger on/off");
                                     app.draw_logger = !app.draw_logger;
@@ -347,25 +539,202 @@ fn tick() -> Receiver<()> {
     rx
 }
 
+// Edits the stack prompt with the pressed key, returns true once new stacks were entered
+fn edit_stack_input(app: &mut App, code: KeyCode) -> bool {
+    let input = app.stack_input.as_mut().unwrap();
+    match code {
+        KeyCode::Char(c) if c.is_ascii_digit() => input.push(c),
+        KeyCode::Backspace => {
+            input.pop();
+        }
+        KeyCode::Enter => {
+            let stacks = input.parse().ok();
+            app.stack_input = None;
+            if let Some(stacks) = stacks {
+                app.stacks.set(stacks);
+                return true;
+            }
+        }
+        KeyCode::Esc => app.stack_input = None,
+        _ => {}
+    }
+    false
+}
+
 fn build_enemy_team_display_data(
-    champion: &ActiveChampion,
+    champion: Option<&dyn Champion>,
     active_player_data: &active_player::Root,
     ability_ranks: AbilityRanks,
+    app: &App,
     opponant_team: teams::OpponantTeam,
-    resistance: resistance::Resistance,
-) -> Vec<Vec<String>> {
+    loadout: &Loadout,
+    targets: Vec<Option<dmg::Target>>,
+) -> (Vec<Vec<String>>, Vec<bool>) {
     let mut ret = Vec::new();
+    let mut killable = Vec::new();
     // Loop to print burst dmg against each enemy champion
-    for i in 0..opponant_team.opponants.len() {
+    for (i, (opponant, target)) in opponant_team.opponants.iter().zip(&targets).enumerate() {
         let mut row = Vec::new();
-        let r = dmg::Resistance::new(resistance.armor[i], resistance.magic_resist[i]);
-        let burst_dmg = dmg::burst_dmg(&champion, &active_player_data, &ability_ranks, r);
-        row.push(opponant_team.opponants[i].0.clone());
-        row.push(opponant_team.opponants[i].1.to_string());
-        row.push(burst_dmg.floor().to_string());
+        row.push(opponant.0.clone());
+        row.push(opponant.1.to_string());
+        // Opponants missing from Data Dragon have no stats to burst
+        match (champion, target) {
+            (Some(champion), Some(target)) => {
+                // Show every rotation, judging lethality by the strongest one
+                let mut max_burst_dmg: f64 = 0.0;
+                let mut strongest = None;
+                for rotation in app.rotations.iter() {
+                    let affordable =
+                        champion.can_afford(active_player_data, &rotation.rotation, &ability_ranks);
+                    if !affordable {
+                        row.push("OOM".to_string());
+                        continue;
+                    }
+                    let burst_dmg = dmg::burst_dmg(
+                        champion,
+                        active_player_data,
+                        loadout,
+                        &ability_ranks,
+                        &rotation.rotation,
+                        target,
+                    );
+                    row.push(burst_dmg.floor().to_string());
+                    if burst_dmg > max_burst_dmg || strongest.is_none() {
+                        strongest = Some(&rotation.rotation);
+                    }
+                    max_burst_dmg = max_burst_dmg.max(burst_dmg);
+                }
+                // Health the enemy dies to the strongest rotation at, as a share of max health
+                let execute = match strongest {
+                    Some(rotation) => format!(
+                        "{:.0}%",
+                        dmg::kill_threshold(
+                            champion,
+                            active_player_data,
+                            loadout,
+                            &ability_ranks,
+                            rotation,
+                            target,
+                        ) / target.max_health
+                            * 100.0
+                    ),
+                    None => "OOM".to_string(),
+                };
+                // Total damage of the strongest rotation when the enemy is grouped up with
+                // the rest of their team
+                match strongest {
+                    Some(rotation) => {
+                        let cluster: Vec<dmg::Target> = std::iter::once(*target)
+                            .chain(
+                                targets
+                                    .iter()
+                                    .enumerate()
+                                    .filter(|(j, _)| *j != i)
+                                    .filter_map(|(_, t)| *t),
+                            )
+                            .take(app.teamfight_targets)
+                            .collect();
+                        let teamfight_dmg = dmg::teamfight_dmg(
+                            champion,
+                            active_player_data,
+                            loadout,
+                            &ability_ranks,
+                            rotation,
+                            &cluster,
+                        );
+                        row.push(teamfight_dmg.floor().to_string());
+                    }
+                    None => row.push("OOM".to_string()),
+                }
+                // Best damage within each trade window, showing the combo of the first. These
+                // are informational, lethality is only judged on the named rotations.
+                let mut combo = String::from("-");
+                for (n, window) in app.trade_windows.iter().enumerate() {
+                    let best = optimizer::best_rotation(
+                        champion,
+                        active_player_data,
+                        loadout,
+                        &ability_ranks,
+                        target,
+                        *window,
+                    );
+                    row.push(best.damage.floor().to_string());
+                    if n == 0 {
+                        // Searches cut short found a strong combo, not necessarily the best
+                        combo = if best.exhaustive {
+                            best.rotation.to_string()
+                        } else {
+                            format!("~{}", best.rotation)
+                        };
+                    }
+                }
+                row.push(combo);
+                // Shields can keep the enemy alive through a burst that beats their health
+                row.push(format!("{:.0}%", max_burst_dmg / target.max_health * 100.0));
+                row.push(format!(
+                    "{:.0}%",
+                    max_burst_dmg / target.effective_health() * 100.0
+                ));
+                row.push(execute);
+                // Magic shields only hold against magic damage, so the burst is dealt out
+                // rather than compared against the total of health and shields
+                killable.push(strongest.is_some_and(|rotation| {
+                    dmg::kills(
+                        champion,
+                        active_player_data,
+                        loadout,
+                        &ability_ranks,
+                        rotation,
+                        target,
+                    )
+                }));
+            }
+            _ => {
+                row.extend(app.rotations.iter().map(|_| "N/A".to_string()));
+                row.push("N/A".to_string());
+                row.extend(app.trade_windows.iter().map(|_| "N/A".to_string()));
+                row.push("-".to_string());
+                row.push("-".to_string());
+                row.push("-".to_string());
+                row.push("-".to_string());
+                killable.push(false);
+            }
+        }
         ret.push(row);
     }
-    ret
+    (ret, killable)
+}
+
+// Combines the resistances, health and shields of each opponant into burst targets, None
+// for opponants missing from Data Dragon
+fn build_targets(
+    resistance: resistance::Resistance,
+    health: health::Health,
+    shields: shields::Shields,
+) -> Vec<Option<dmg::Target>> {
+    health
+        .max_health
+        .iter()
+        .enumerate()
+        .map(|(i, max_health)| {
+            Some(dmg::Target {
+                resistance: dmg::Resistance::new(
+                    resistance.armor[i]?,
+                    resistance.bonus_armor[i],
+                    resistance.magic_resist[i]?,
+                    resistance.bonus_magic_resist[i],
+                ),
+                max_health: (*max_health)?,
+                // The Live Client doesn't report the health of opponants, so bursts start at
+                // full health and execute thresholds show how low they have to be
+                current_health: (*max_health)?,
+                shield: shields.shield[i],
+                magic_shield: shields.magic_shield[i],
+                spell_shield: shields.spell_shield[i],
+                position: 0,
+            })
+        })
+        .collect()
 }
 
 fn get_total_gold_earned(current_gold: &f64, gold_last_tick: &f64, gold_total: &f64) -> f64 {
@@ -0,0 +1,183 @@
+use crate::{
+    active_player,
+    champions::definition::ChampionDefinition,
+    dmg::{self, Damage},
+    item_effects::ItemEffect,
+    loadout::{CritMode, Loadout},
+};
+
+// Multiplier applied to auto attack damage for critical strikes
+pub fn crit_multiplier(active_player: &active_player::Root, crit_mode: CritMode) -> f64 {
+    let stats = &active_player.champion_stats;
+    let crit_chance = stats.crit_chance.clamp(0.0, 1.0);
+    // The Live Client reports crit damage as a percentage of attack damage
+    let crit_damage = stats.crit_damage / 100.0;
+    match crit_mode {
+        CritMode::Expected => 1.0 + crit_chance * (crit_damage - 1.0),
+        CritMode::Max if crit_chance > 0.0 => crit_damage,
+        CritMode::Max => 1.0,
+    }
+}
+
+// Crits only multiply the attack damage an auto attack deals, the physical part. Magic and
+// true damage champions add to their autos, like Thresh's Flay, don't crit.
+pub fn crit(attack: Damage, active_player: &active_player::Root, crit_mode: CritMode) -> Damage {
+    Damage {
+        physical: attack.physical * crit_multiplier(active_player, crit_mode),
+        ..attack
+    }
+}
+
+// On-hit effects of the active player's items against the target
+pub fn on_hit_effects(
+    active_player: &active_player::Root,
+    definition: &ChampionDefinition,
+    loadout: &Loadout,
+    target: &dmg::Target,
+) -> Vec<Damage> {
+    loadout
+        .item_effects
+        .iter()
+        .filter_map(|(_, effect)| match effect {
+            ItemEffect::OnHit(damage) => Some(damage(active_player, definition, target)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        all_players, champions::definition, dmg::DamageType, item_effects, runes::Runes,
+        summoners::SummonerSpell,
+    };
+
+    // Base crit damage is 175% of attack damage, Infinity Edge raises it to 210%
+    const BASE_CRIT_DAMAGE: f64 = 175.0;
+    const INFINITY_EDGE_CRIT_DAMAGE: f64 = 210.0;
+
+    const DORANS_BLADE: i64 = 1055;
+    const NASHORS_TOOTH: i64 = 3115;
+    const BLADE_OF_THE_RUINED_KING: i64 = 3153;
+
+    fn player(crit_chance: f64, crit_damage: f64) -> active_player::Root {
+        let mut player = active_player::Root {
+            level: 9,
+            ..Default::default()
+        };
+        player.champion_stats.crit_chance = crit_chance;
+        player.champion_stats.crit_damage = crit_damage;
+        player.champion_stats.ability_power = 100.0;
+        player.champion_stats.attack_range = 550.0;
+        player
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-6,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn no_crit_chance_never_crits() {
+        for crit_mode in [CritMode::Expected, CritMode::Max] {
+            assert_close(
+                crit_multiplier(&player(0.0, BASE_CRIT_DAMAGE), crit_mode),
+                1.0,
+            );
+        }
+    }
+
+    #[test]
+    fn partial_crit_chance_is_averaged_or_assumed_to_crit() {
+        let player = player(0.4, BASE_CRIT_DAMAGE);
+        assert_close(crit_multiplier(&player, CritMode::Expected), 1.3);
+        assert_close(crit_multiplier(&player, CritMode::Max), 1.75);
+    }
+
+    #[test]
+    fn full_crit_chance_always_crits() {
+        for crit_mode in [CritMode::Expected, CritMode::Max] {
+            assert_close(
+                crit_multiplier(&player(1.0, BASE_CRIT_DAMAGE), crit_mode),
+                1.75,
+            );
+            // Crit chance above 100% doesn't crit any harder
+            assert_close(
+                crit_multiplier(&player(1.2, BASE_CRIT_DAMAGE), crit_mode),
+                1.75,
+            );
+        }
+    }
+
+    #[test]
+    fn infinity_edge_raises_crit_damage() {
+        let partial = player(0.6, INFINITY_EDGE_CRIT_DAMAGE);
+        assert_close(crit_multiplier(&partial, CritMode::Expected), 1.66);
+        assert_close(crit_multiplier(&partial, CritMode::Max), 2.1);
+        let full = player(1.0, INFINITY_EDGE_CRIT_DAMAGE);
+        assert_close(crit_multiplier(&full, CritMode::Expected), 2.1);
+    }
+
+    #[test]
+    fn only_the_physical_part_of_an_attack_crits() {
+        let attack =
+            Damage::new(100.0, DamageType::Physical) + Damage::new(50.0, DamageType::Magic);
+        let critted = crit(attack, &player(1.0, BASE_CRIT_DAMAGE), CritMode::Expected);
+        assert_close(critted.physical, 175.0);
+        assert_close(critted.magic, 50.0);
+    }
+
+    #[test]
+    fn on_hit_effects_come_from_the_items() {
+        let definition =
+            definition::load_definitions("./resources/champions").unwrap()["Lux"].clone();
+        let items: Vec<all_players::Item> = (0..)
+            .zip([DORANS_BLADE, NASHORS_TOOTH, BLADE_OF_THE_RUINED_KING])
+            .map(|(slot, item_id)| all_players::Item {
+                item_id,
+                slot,
+                count: 1,
+                ..Default::default()
+            })
+            .collect();
+        let loadout = Loadout {
+            item_effects: item_effects::item_effects(&items),
+            summoner_spells: [SummonerSpell::Other; 2],
+            runes: Runes::default(),
+            crit_mode: CritMode::Expected,
+        };
+        let target = dmg::Target {
+            resistance: dmg::Resistance::new(0.0, 0.0, 0.0, 0.0),
+            max_health: 2000.0,
+            current_health: 1000.0,
+            shield: 0.0,
+            magic_shield: 0.0,
+            spell_shield: false,
+            position: 0,
+        };
+        let on_hits = on_hit_effects(
+            &player(0.0, BASE_CRIT_DAMAGE),
+            &definition,
+            &loadout,
+            &target,
+        );
+        // Doran's Blade has no on-hit effect, Nashor's Tooth deals 15 + 20% AP and a ranged
+        // Blade of the Ruined King 9% of current health
+        assert_eq!(on_hits.len(), 2);
+        let total: Damage = on_hits.into_iter().sum();
+        assert_close(total.magic, 35.0);
+        assert_close(total.physical, 90.0);
+
+        let no_items = Loadout {
+            item_effects: Vec::new(),
+            ..loadout
+        };
+        let player = player(0.0, BASE_CRIT_DAMAGE);
+        assert!(on_hit_effects(&player, &definition, &no_items, &target).is_empty());
+    }
+}
@@ -1,15 +1,836 @@
+pub mod ahri;
+pub mod brand;
+pub mod cassiopeia;
+pub mod definition;
+#[cfg(test)]
+pub mod fixtures;
+pub mod garen;
+pub mod leblanc;
+pub mod lux;
+pub mod nasus;
 pub mod orianna;
+pub mod pyke;
+pub mod senna;
+pub mod sion;
+pub mod smolder;
+pub mod syndra;
+pub mod thresh;
+pub mod veigar;
+pub mod velkoz;
+pub mod viktor;
+pub mod xerath;
+pub mod zoe;
 
-#[derive(Debug)]
-pub enum ActiveChampion {
-    Orianna(orianna::Orianna),
-    None,
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use crate::{
+    active_player::{self, AbilityRanks},
+    attack,
+    dmg::{self, Damage, DamageType},
+    item_effects,
+    loadout::Loadout,
+    rotation::{Action, Rotation, Step},
+    runes,
+    stacks::StackSource,
+};
+use definition::ChampionDefinition;
+
+// An ability that can be used as a step in a rotation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum Ability {
+    Q,
+    W,
+    E,
+    R,
+    Passive,
+    Attack,
+}
+
+impl Ability {
+    // Maps a rotation character to the ability it stands for
+    pub fn from_char(c: char) -> Option<Ability> {
+        match c {
+            'Q' => Some(Ability::Q),
+            'W' => Some(Ability::W),
+            'E' => Some(Ability::E),
+            'R' => Some(Ability::R),
+            'P' => Some(Ability::Passive),
+            'A' => Some(Ability::Attack),
+            _ => None,
+        }
+    }
+
+    // Whether the active player has put a point into the ability
+    pub fn is_learned(&self, ability_ranks: &AbilityRanks) -> bool {
+        match self {
+            Ability::Q => ability_ranks.q_rank > 0,
+            Ability::W => ability_ranks.w_rank > 0,
+            Ability::E => ability_ranks.e_rank > 0,
+            Ability::R => ability_ranks.r_rank > 0,
+            Ability::Passive | Ability::Attack => true,
+        }
+    }
+}
+
+pub trait Champion: std::fmt::Debug {
+    // Live Client name of the champion
+    fn name(&self) -> &str {
+        &self.definition().name
+    }
+
+    // Definition the champion's damage is built from
+    fn definition(&self) -> &ChampionDefinition;
+
+    // Damage of a single cast of the ability before mitigation
+    fn raw_damage(
+        &self,
+        ability: Ability,
+        active_player: &active_player::Root,
+        ability_ranks: &AbilityRanks,
+    ) -> Damage {
+        self.definition()
+            .raw_damage(ability, active_player, ability_ranks)
+    }
+
+    // Damage of the passive's next proc on a target it already procced on the given number
+    // of times, for passives that grow with consecutive hits
+    fn passive_damage(
+        &self,
+        active_player: &active_player::Root,
+        ability_ranks: &AbilityRanks,
+        _procs: u32,
+    ) -> Damage {
+        self.raw_damage(Ability::Passive, active_player, ability_ranks)
+    }
+
+    // Damage the champion's passive adds to a step that hits the target, given the steps used
+    // before it in the rotation
+    fn passive_bonus(
+        &self,
+        _previous: &[Step],
+        _step: &Step,
+        _active_player: &active_player::Root,
+        _ability_ranks: &AbilityRanks,
+        _target: &dmg::Target,
+    ) -> Damage {
+        Damage::default()
+    }
+
+    // Whether passive_bonus depends on the steps used before, which the optimizer then can't
+    // treat two sequences reaching the same point as equivalent for
+    fn passive_reads_previous(&self) -> bool {
+        false
+    }
+
+    // Whether auto attacks proc the passive on top of their own damage
+    fn passive_on_attack(&self) -> bool {
+        false
+    }
+
+    // Damage of a single cast of the ability that scales with the target's health when it hits
+    fn target_damage(
+        &self,
+        ability: Ability,
+        active_player: &active_player::Root,
+        ability_ranks: &AbilityRanks,
+        target: &dmg::Target,
+    ) -> Damage {
+        let definition = match self.definition().abilities.get(&ability) {
+            Some(definition) => definition,
+            None => return Damage::default(),
+        };
+        if self
+            .definition()
+            .rank(ability, active_player, ability_ranks)
+            < 1
+        {
+            return Damage::default();
+        }
+        let ratios = &definition.ratios;
+        let per_hit = ratios.target_max_health * target.max_health
+            + ratios.target_current_health * target.current_health
+            + ratios.target_missing_health * target.missing_health();
+        let total = per_hit * definition.hits as f64;
+        match definition.damage_type {
+            DamageType::Mixed => Damage::split(total, definition.physical_share),
+            damage_type => Damage::new(total, damage_type),
+        }
+    }
+
+    // Multiplier of the ability's damage against a target missing health
+    fn missing_health_multiplier(&self, ability: Ability, target: &dmg::Target) -> f64 {
+        let amp = match self.definition().abilities.get(&ability) {
+            Some(definition) => &definition.missing_health_amp,
+            None => return 1.0,
+        };
+        match amp {
+            Some(amp) if target.max_health > 0.0 => {
+                1.0 + (amp.per_missing * target.missing_health() / target.max_health).min(amp.max)
+            }
+            _ => 1.0,
+        }
+    }
+
+    // Health at or below which the ability kills the target outright, None if it can't
+    fn execute_threshold(
+        &self,
+        _ability: Ability,
+        _active_player: &active_player::Root,
+        _ability_ranks: &AbilityRanks,
+    ) -> Option<f64> {
+        None
+    }
+
+    // Stat the champion's stacks grant, None for champions without stacks
+    fn stack_source(&self) -> Option<StackSource> {
+        None
+    }
+
+    // Updates the stacks the champion's damage scales with
+    fn set_stacks(&mut self, _stacks: u32) {}
+
+    // Whether the damage reads the stack count given to set_stacks. Champions whose stacks
+    // only grant stats already scale with them through the stats, so their count is shown
+    // for information.
+    fn scales_with_stacks(&self) -> bool {
+        false
+    }
+
+    // Most enemies a single cast of the ability can hit
+    fn max_targets(&self, ability: Ability) -> u32 {
+        match self.definition().abilities.get(&ability) {
+            Some(definition) => definition.targets,
+            None => 1,
+        }
+    }
+
+    // Share of the ability's damage dealt to the enemy hit at the given position
+    fn target_multiplier(&self, _ability: Ability, _position: u32) -> f64 {
+        1.0
+    }
+
+    // Seconds the champion is busy using the ability
+    fn cast_time(&self, ability: Ability, active_player: &active_player::Root) -> f64 {
+        match (ability, self.definition().abilities.get(&ability)) {
+            (Ability::Attack, _) => 1.0 / active_player.champion_stats.attack_speed,
+            (_, Some(definition)) => definition.cast_time,
+            (_, None) => 0.0,
+        }
+    }
+
+    // Cooldown of the ability in seconds after ability haste, None if it isn't known
+    fn cooldown(
+        &self,
+        ability: Ability,
+        active_player: &active_player::Root,
+        ability_ranks: &AbilityRanks,
+    ) -> Option<f64> {
+        let definition = self.definition().abilities.get(&ability)?;
+        let rank = self
+            .definition()
+            .rank(ability, active_player, ability_ranks);
+        if rank < 1 || definition.cooldown.is_empty() {
+            return None;
+        }
+        let cooldown = definition.cooldown[(rank as usize).min(definition.cooldown.len()) - 1];
+        Some(cooldown * 100.0 / (100.0 + active_player.champion_stats.ability_haste))
+    }
+
+    // Resource cost of the ability, 0 if the champion doesn't use mana or energy
+    fn cost(
+        &self,
+        ability: Ability,
+        active_player: &active_player::Root,
+        ability_ranks: &AbilityRanks,
+    ) -> f64 {
+        if !uses_resource(active_player) {
+            return 0.0;
+        }
+        let definition = match self.definition().abilities.get(&ability) {
+            Some(definition) => definition,
+            None => return 0.0,
+        };
+        let rank = self
+            .definition()
+            .rank(ability, active_player, ability_ranks);
+        if rank < 1 || definition.cost.is_empty() {
+            return 0.0;
+        }
+        definition.cost[(rank as usize).min(definition.cost.len()) - 1]
+    }
+
+    // Whether the active player has enough resource to use every step in the rotation
+    fn can_afford(
+        &self,
+        active_player: &active_player::Root,
+        rotation: &Rotation,
+        ability_ranks: &AbilityRanks,
+    ) -> bool {
+        let cost: f64 = rotation
+            .steps
+            .iter()
+            .map(|step| match step.action {
+                Action::Ability(ability) => {
+                    if step.optional && !ability.is_learned(ability_ranks) {
+                        return 0.0;
+                    }
+                    self.cost(ability, active_player, ability_ranks)
+                }
+                Action::SummonerSpell(_) | Action::ItemActive(_) => 0.0,
+            })
+            .sum();
+        cost <= active_player.champion_stats.resource_value
+    }
+
+    // Damage of a single step before mitigation, along with the kind of hit it lands for
+    // runes to proc on. passive_procs is the number of times the passive already procced.
+    fn step_damage(
+        &self,
+        step: &Step,
+        active_player: &active_player::Root,
+        loadout: &Loadout,
+        ability_ranks: &AbilityRanks,
+        target: &dmg::Target,
+        passive_procs: u32,
+    ) -> (Damage, Option<runes::Hit>) {
+        // Steps only reach enemies behind the main target when they hit several enemies
+        let targets = match step.action {
+            Action::Ability(ability) => step.targets.unwrap_or(1).min(self.max_targets(ability)),
+            Action::SummonerSpell(_) | Action::ItemActive(_) => 1,
+        };
+        if target.position >= targets {
+            return (Damage::default(), None);
+        }
+        match step.action {
+            Action::Ability(Ability::Attack) => {
+                // Auto attacks crit and apply on-hit effects from items, which don't crit
+                let attack = self.raw_damage(Ability::Attack, active_player, ability_ranks);
+                let mut damage = attack::crit(attack, active_player, loadout.crit_mode)
+                    + attack::on_hit_effects(active_player, self.definition(), loadout, target)
+                        .into_iter()
+                        .sum();
+                if self.passive_on_attack() {
+                    damage += self.passive_damage(active_player, ability_ranks, passive_procs);
+                }
+                (damage, Some(runes::Hit::Attack))
+            }
+            // Passive procs ride along other hits, so they don't proc runes on their own
+            Action::Ability(Ability::Passive) => (
+                self.passive_damage(active_player, ability_ranks, passive_procs),
+                None,
+            ),
+            Action::Ability(ability) => {
+                if step.optional && !ability.is_learned(ability_ranks) {
+                    return (Damage::default(), None);
+                }
+                // Executes kill through shields, so they deal whatever the target has left.
+                // Magic shields don't hold against true damage and aren't counted.
+                if let Some(threshold) =
+                    self.execute_threshold(ability, active_player, ability_ranks)
+                {
+                    if target.current_health <= threshold {
+                        return (
+                            Damage::new(target.current_health + target.shield, DamageType::True),
+                            Some(runes::Hit::Ability),
+                        );
+                    }
+                }
+                let damage = (self.raw_damage(ability, active_player, ability_ranks)
+                    + self.target_damage(ability, active_player, ability_ranks, target))
+                    * self.target_multiplier(ability, target.position)
+                    * self.missing_health_multiplier(ability, target);
+                let hit = if damage.total() > 0.0 {
+                    Some(runes::Hit::Ability)
+                } else {
+                    None
+                };
+                (damage, hit)
+            }
+            Action::SummonerSpell(n) => (
+                loadout.summoner_spells[n as usize - 1].damage(active_player),
+                None,
+            ),
+            // Item actives depend on the inventory and are added by the item procs
+            Action::ItemActive(_) => (Damage::default(), None),
+        }
+    }
+
+    // Sums the mitigated damage of every step in the rotation against a single target
+    fn calculate_damage(
+        &self,
+        active_player: &active_player::Root,
+        loadout: &Loadout,
+        rotation: &Rotation,
+        ability_ranks: &AbilityRanks,
+        target: &dmg::Target,
+    ) -> f64 {
+        deal_rotation(
+            self,
+            active_player,
+            loadout,
+            rotation,
+            ability_ranks,
+            target,
+        )
+        .dealt
+    }
+
+    // The health and shields the target has left after the rotation
+    fn damaged_target(
+        &self,
+        active_player: &active_player::Root,
+        loadout: &Loadout,
+        rotation: &Rotation,
+        ability_ranks: &AbilityRanks,
+        target: &dmg::Target,
+    ) -> dmg::Target {
+        deal_rotation(
+            self,
+            active_player,
+            loadout,
+            rotation,
+            ability_ranks,
+            target,
+        )
+        .target
+    }
+}
+
+// Everything about a burst so far that changes the damage of later steps, except the steps
+// champion passives read
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BurstState {
+    procs: runes::ProcState,
+    items: item_effects::ProcState,
+    passive_procs: u32,
+    // Health and shields left on the target, to a hundredth of a point
+    target: [i64; 3],
+    spell_shield: bool,
+}
+
+// Damage dealt by a whole rotation and the target it leaves behind
+struct Dealt {
+    dealt: f64,
+    target: dmg::Target,
+}
+
+fn deal_rotation<C: Champion + ?Sized>(
+    champion: &C,
+    active_player: &active_player::Root,
+    loadout: &Loadout,
+    rotation: &Rotation,
+    ability_ranks: &AbilityRanks,
+    target: &dmg::Target,
+) -> Dealt {
+    let conqueror = runes::conqueror_player(&loadout.runes, champion.definition(), active_player);
+    let mut burst = Burst::new(
+        champion.definition(),
+        active_player,
+        loadout,
+        ability_ranks,
+        target,
+        conqueror.as_ref(),
+    );
+    for (i, step) in rotation.steps.iter().enumerate() {
+        burst.step(champion, &rotation.steps[..i], step);
+    }
+    Dealt {
+        dealt: burst.dealt,
+        target: burst.target,
+    }
+}
+
+// Damage dealt to a single target over the steps of a rotation, dealt one step at a time so
+// rotations sharing their first steps can continue from the same point
+#[derive(Clone)]
+pub struct Burst<'a> {
+    active_player: &'a active_player::Root,
+    loadout: &'a Loadout,
+    ability_ranks: &'a AbilityRanks,
+    // The target with the health and shields the steps so far left
+    target: dmg::Target,
+    // The active player with Conqueror fully stacked, see runes::conqueror_player
+    conqueror: Option<&'a active_player::Root>,
+    penetrations: dmg::Penetrations,
+    procs: runes::Procs<'a>,
+    items: item_effects::Procs<'a>,
+    passive_procs: u32,
+    pub dealt: f64,
+}
+
+impl<'a> Burst<'a> {
+    pub fn new(
+        definition: &'a ChampionDefinition,
+        active_player: &'a active_player::Root,
+        loadout: &'a Loadout,
+        ability_ranks: &'a AbilityRanks,
+        target: &'a dmg::Target,
+        conqueror: Option<&'a active_player::Root>,
+    ) -> Self {
+        Burst {
+            active_player,
+            loadout,
+            ability_ranks,
+            target: *target,
+            conqueror,
+            penetrations: dmg::Penetrations::new(active_player),
+            procs: runes::Procs::new(&loadout.runes, definition, active_player, target.max_health),
+            items: item_effects::Procs::new(&loadout.item_effects, active_player, definition),
+            passive_procs: 0,
+            dealt: 0.0,
+        }
+    }
+
+    pub fn state(&self) -> BurstState {
+        let hundredths = |value: f64| (value * 100.0).round() as i64;
+        BurstState {
+            procs: self.procs.state(),
+            items: self.items.state(),
+            passive_procs: self.passive_procs,
+            target: [
+                hundredths(self.target.current_health),
+                hundredths(self.target.shield),
+                hundredths(self.target.magic_shield),
+            ],
+            spell_shield: self.target.spell_shield,
+        }
+    }
+
+    // Deals the step after the previous steps of the rotation
+    pub fn step<C: Champion + ?Sized>(&mut self, champion: &C, previous: &[Step], step: &Step) {
+        let (active_player, loadout, ability_ranks) =
+            (self.active_player, self.loadout, self.ability_ranks);
+        // Each step hits the target with the health and shields the steps before left
+        let target = &self.target;
+        let (mut damage, hit) = champion.step_damage(
+            step,
+            active_player,
+            loadout,
+            ability_ranks,
+            target,
+            self.passive_procs,
+        );
+        // Damage scales linearly with stats, so damage while Conqueror is stacking is
+        // interpolated between the current stats and the fully stacked stats
+        if let (Some(stacked), Some(_)) = (self.conqueror, hit) {
+            let (stacked_damage, _) = champion.step_damage(
+                step,
+                stacked,
+                loadout,
+                ability_ranks,
+                target,
+                self.passive_procs,
+            );
+            let stacks = self.procs.conqueror_stacks();
+            damage = damage * (1.0 - stacks) + stacked_damage * stacks;
+        }
+        // Spell shields block the first ability to hit along with everything it would proc
+        if hit == Some(runes::Hit::Ability) && target.spell_shield {
+            self.target.spell_shield = false;
+            return;
+        }
+        if hit.is_some() {
+            damage += champion.passive_bonus(previous, step, active_player, ability_ranks, target);
+        }
+        let mut proc_damage = match (step.action, hit) {
+            (Action::ItemActive(slot), _) => self.items.active(slot, target),
+            (_, Some(runes::Hit::Ability)) => self.items.ability_hit(target),
+            (_, Some(runes::Hit::Attack)) => self.items.attack_hit(target),
+            _ => Damage::default(),
+        };
+        let amplifier = self.procs.amplifier(target.current_health) * self.items.amplifier();
+        if let Some(hit) = hit {
+            proc_damage += self.procs.hit(hit, target.current_health);
+        }
+        // Runes and item effects proc on the main target only, the enemies behind it
+        // only take the step's own damage
+        if target.position == 0 {
+            damage += proc_damage;
+        }
+        let procs_passive = match step.action {
+            Action::Ability(Ability::Passive) => true,
+            Action::Ability(Ability::Attack) => champion.passive_on_attack(),
+            _ => false,
+        };
+        if procs_passive {
+            self.passive_procs += 1;
+        }
+        let magic = Damage::new(damage.magic, DamageType::Magic);
+        let magic = dmg::mitigate(magic, &target.resistance, &self.penetrations) * amplifier;
+        let dealt = dmg::mitigate(damage, &target.resistance, &self.penetrations) * amplifier;
+        self.target = target.damaged(magic, dealt - magic);
+        self.dealt += dealt;
+    }
+}
+
+// Abilities used by the steps, skipping optional abilities that haven't been learned
+pub fn used_abilities<'a>(
+    steps: &'a [Step],
+    ability_ranks: &'a AbilityRanks,
+) -> impl DoubleEndedIterator<Item = Ability> + 'a {
+    steps.iter().filter_map(|step| match step.action {
+        Action::Ability(ability) if !step.optional || ability.is_learned(ability_ranks) => {
+            Some(ability)
+        }
+        _ => None,
+    })
+}
+
+// Steps after the last step using an ability matching the predicate, every step if none does
+pub fn after_last(steps: &[Step], matches: impl Fn(Ability) -> bool) -> &[Step] {
+    let last = steps.iter().rposition(|step| match step.action {
+        Action::Ability(ability) => matches(ability),
+        _ => false,
+    });
+    match last {
+        Some(i) => &steps[i + 1..],
+        None => steps,
+    }
+}
+
+// Whether the ability is one of the champion's basic or ultimate abilities
+pub fn is_spell(ability: Ability) -> bool {
+    matches!(ability, Ability::Q | Ability::W | Ability::E | Ability::R)
 }
 
-pub fn match_champion(name: &str) -> ActiveChampion {
-    match name {
-        "Orianna" => ActiveChampion::Orianna(orianna::Orianna::build()),
-        _ => ActiveChampion::None,
+// Whether the active player's abilities cost a resource that runs out
+pub fn uses_resource(active_player: &active_player::Root) -> bool {
+    matches!(
+        active_player.champion_stats.resource_type.as_str(),
+        "MANA" | "ENERGY"
+    )
+}
+
+type ChampionBuilder = fn(ChampionDefinition) -> Box<dyn Champion>;
+
+// Champions with a custom implementation keyed by their Live Client champion name
+fn registry() -> HashMap<&'static str, ChampionBuilder> {
+    let mut registry: HashMap<&'static str, ChampionBuilder> = HashMap::new();
+    registry.insert("Ahri", |d| Box::new(ahri::Ahri::build(d)));
+    registry.insert("Brand", |d| Box::new(brand::Brand::build(d)));
+    registry.insert("Cassiopeia", |d| Box::new(cassiopeia::Cassiopeia::build(d)));
+    registry.insert("Garen", |d| Box::new(garen::Garen::build(d)));
+    registry.insert("LeBlanc", |d| Box::new(leblanc::LeBlanc::build(d)));
+    registry.insert("Lux", |d| Box::new(lux::Lux::build(d)));
+    registry.insert("Nasus", |d| Box::new(nasus::Nasus::build(d)));
+    registry.insert("Orianna", |d| Box::new(orianna::Orianna::build(d)));
+    registry.insert("Pyke", |d| Box::new(pyke::Pyke::build(d)));
+    registry.insert("Senna", |d| Box::new(senna::Senna::build(d)));
+    registry.insert("Sion", |d| Box::new(sion::Sion::build(d)));
+    registry.insert("Smolder", |d| Box::new(smolder::Smolder::build(d)));
+    registry.insert("Syndra", |d| Box::new(syndra::Syndra::build(d)));
+    registry.insert("Thresh", |d| Box::new(thresh::Thresh::build(d)));
+    registry.insert("Veigar", |d| Box::new(veigar::Veigar::build(d)));
+    registry.insert("Vel'Koz", |d| Box::new(velkoz::VelKoz::build(d)));
+    registry.insert("Viktor", |d| Box::new(viktor::Viktor::build(d)));
+    registry.insert("Xerath", |d| Box::new(xerath::Xerath::build(d)));
+    registry.insert("Zoe", |d| Box::new(zoe::Zoe::build(d)));
+    registry
+}
+
+// Builds the champion from its definition, falling back to the plain definition
+// when the champion has no custom implementation
+pub fn match_champion(
+    name: &str,
+    definitions: &HashMap<String, ChampionDefinition>,
+) -> Option<Box<dyn Champion>> {
+    let definition = definitions.get(name)?.clone();
+    match registry().get(name) {
+        Some(build) => Some(build(definition)),
+        None => Some(Box::new(definition)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        champions::fixtures::{assert_close, burst, champion, loadout, player, ranks, target},
+        runes::Runes,
+    };
+
+    // Damage of the rotation against identical enemies clustered together, using the runes
+    fn teamfight(champion: &dyn Champion, runes: Runes, rotation: &str, targets: usize) -> f64 {
+        dmg::teamfight_dmg(
+            champion,
+            &player(9, 100.0),
+            &loadout(runes),
+            &ranks(0, 0, 1, 0),
+            &rotation.parse().expect("Invalid rotation"),
+            &vec![target(2000.0, 2000.0); targets],
+        )
+    }
+
+    #[test]
+    fn runes_only_proc_on_the_main_teamfight_target() {
+        let lux = champion("Lux");
+        let comet = Runes {
+            keystone: 8229,
+            general: Vec::new(),
+        };
+        let without = teamfight(lux.as_ref(), Runes::default(), "E", 3);
+        let with = teamfight(lux.as_ref(), comet.clone(), "E", 3);
+        let single_target = teamfight(lux.as_ref(), comet, "E", 1)
+            - teamfight(lux.as_ref(), Runes::default(), "E", 1);
+        assert!(single_target > 0.0);
+        assert_close(with - without, single_target);
+    }
+
+    #[test]
+    fn conqueror_damage_is_interpolated_while_stacking() {
+        let lux = champion("Lux");
+        let ranks = ranks(0, 0, 1, 0);
+        let conqueror = Runes {
+            keystone: 8010,
+            general: Vec::new(),
+        };
+        let e = |ability_power| burst(lux.as_ref(), &player(9, ability_power), &ranks, "E", 2000.0);
+        // Without any attack range Lux stacks like a melee champion, gaining 2 of the 12
+        // stacks per hit, and the first hit lands without any
+        let adaptive_force = (2.0 + 2.5 * 8.0 / 17.0) * 12.0;
+        let stacked = e(100.0 + adaptive_force);
+        assert_close(
+            lux.calculate_damage(
+                &player(9, 100.0),
+                &loadout(conqueror),
+                &"EE".parse().unwrap(),
+                &ranks,
+                &target(2000.0, 2000.0),
+            ),
+            e(100.0) + e(100.0) * 10.0 / 12.0 + stacked * 2.0 / 12.0,
+        );
+    }
+
+    #[test]
+    fn magic_shields_keep_enemies_alive_through_magic_bursts_only() {
+        let annie = champion("Annie");
+        let loadout = loadout(Runes::default());
+        // Disintegrate deals 230 magic damage
+        let kills = |shield, magic_shield| {
+            let target = dmg::Target {
+                shield,
+                magic_shield,
+                ..target(2000.0, 200.0)
+            };
+            dmg::kills(
+                annie.as_ref(),
+                &player(7, 100.0),
+                &loadout,
+                &ranks(3, 1, 1, 1),
+                &"Q".parse().unwrap(),
+                &target,
+            )
+        };
+        assert!(kills(0.0, 0.0));
+        assert!(!kills(0.0, 50.0));
+        assert!(!kills(50.0, 0.0));
+        assert!(kills(20.0, 0.0));
+    }
+
+    #[test]
+    fn spell_shields_block_the_first_ability_only() {
+        let annie = champion("Annie");
+        let loadout = loadout(Runes::default());
+        let target = dmg::Target {
+            spell_shield: true,
+            ..target(2000.0, 2000.0)
+        };
+        let player = player(7, 100.0);
+        let damage = |rotation: &str| {
+            annie.calculate_damage(
+                &player,
+                &loadout,
+                &rotation.parse().unwrap(),
+                &ranks(3, 1, 1, 1),
+                &target,
+            )
+        };
+        assert_eq!(damage("Q"), 0.0);
+        assert_close(damage("QQ"), 230.0);
+        // Auto attacks go through and leave the spell shield up
+        assert_close(damage("AQ"), player.champion_stats.attack_damage);
+    }
+
+    #[test]
+    fn cooldowns_scale_with_ability_haste() {
+        let lux = champion("Lux");
+        let mut player = player(6, 0.0);
+        // Light Binding's cooldown is 11 seconds at rank 1 and 9 from rank 5
+        assert_eq!(
+            lux.cooldown(Ability::Q, &player, &ranks(1, 0, 0, 0)),
+            Some(11.0)
+        );
+        assert_eq!(
+            lux.cooldown(Ability::Q, &player, &ranks(5, 0, 0, 0)),
+            Some(9.0)
+        );
+        player.champion_stats.ability_haste = 100.0;
+        assert_eq!(
+            lux.cooldown(Ability::Q, &player, &ranks(1, 0, 0, 0)),
+            Some(5.5)
+        );
+        assert_close(
+            lux.cooldown(Ability::Q, &player, &ranks(5, 0, 0, 0))
+                .unwrap(),
+            4.5,
+        );
+        // Unlearned abilities and abilities without cooldown data have no cooldown
+        assert_eq!(lux.cooldown(Ability::Q, &player, &ranks(0, 0, 0, 0)), None);
+        assert_eq!(
+            lux.cooldown(Ability::Passive, &player, &ranks(1, 0, 0, 0)),
+            None
+        );
+    }
+
+    #[test]
+    fn costs_are_paid_from_mana() {
+        let lux = champion("Lux");
+        let mut player = player(6, 0.0);
+        player.champion_stats.resource_type = "MANA".to_string();
+        // Lucent Singularity costs 70 mana at rank 1 and 110 at rank 5
+        assert_eq!(lux.cost(Ability::E, &player, &ranks(0, 0, 1, 0)), 70.0);
+        assert_eq!(lux.cost(Ability::E, &player, &ranks(0, 0, 5, 0)), 110.0);
+        assert_eq!(lux.cost(Ability::E, &player, &ranks(0, 0, 0, 0)), 0.0);
+        assert_eq!(lux.cost(Ability::Attack, &player, &ranks(0, 0, 1, 0)), 0.0);
+        // Champions without mana or energy never pay for their abilities
+        player.champion_stats.resource_type = "NONE".to_string();
+        assert_eq!(lux.cost(Ability::E, &player, &ranks(0, 0, 1, 0)), 0.0);
+    }
+
+    #[test]
+    fn rotations_costing_more_than_the_current_mana_are_out_of_mana() {
+        let lux = champion("Lux");
+        let ranks = ranks(1, 0, 1, 0);
+        let mut player = player(6, 0.0);
+        player.champion_stats.resource_type = "MANA".to_string();
+        player.champion_stats.resource_value = 120.0;
+        let can_afford = |player: &active_player::Root, rotation: &str| {
+            lux.can_afford(player, &rotation.parse().unwrap(), &ranks)
+        };
+        assert!(can_afford(&player, "QEAS1I0"));
+        assert!(!can_afford(&player, "QQE"));
+        // Optional steps that haven't been learned cost nothing
+        assert!(can_afford(&player, "QER?"));
+        player.champion_stats.resource_value = 119.0;
+        assert!(!can_afford(&player, "QE"));
+        player.champion_stats.resource_type = "NONE".to_string();
+        assert!(can_afford(&player, "QQEE"));
+    }
+
+    #[test]
+    fn explicit_single_target_steps_stay_single_target_in_teamfights() {
+        let lux = champion("Lux");
+        let single_target = teamfight(lux.as_ref(), Runes::default(), "E", 1);
+        assert_close(
+            teamfight(lux.as_ref(), Runes::default(), "E@1", 3),
+            single_target,
+        );
+        assert_close(
+            teamfight(lux.as_ref(), Runes::default(), "E", 3),
+            3.0 * single_target,
+        );
     }
 }
 
@@ -0,0 +1,58 @@
+use super::{definition::ChampionDefinition, Ability, Champion};
+use crate::{
+    active_player,
+    dmg::{Damage, DamageType},
+    AbilityRanks,
+};
+
+// Fox-Fire's three flames all hit a lone target, the second and third for 30% damage
+const FOX_FIRE_FOLLOW_UPS: f64 = 2.0;
+const FOX_FIRE_FOLLOW_UP_DAMAGE: f64 = 0.3;
+
+#[derive(Debug)]
+pub struct Ahri {
+    definition: ChampionDefinition,
+}
+
+impl Ahri {
+    pub fn build(definition: ChampionDefinition) -> Ahri {
+        Ahri { definition }
+    }
+}
+
+impl Champion for Ahri {
+    fn definition(&self) -> &ChampionDefinition {
+        &self.definition
+    }
+
+    fn raw_damage(
+        &self,
+        ability: Ability,
+        active_player: &active_player::Root,
+        ability_ranks: &AbilityRanks,
+    ) -> Damage {
+        let damage = self
+            .definition
+            .raw_damage(ability, active_player, ability_ranks);
+        match ability {
+            // Orb of Deception deals magic damage on the way out and true damage on the way back
+            Ability::Q => damage + Damage::new(damage.total(), DamageType::True),
+            Ability::W => damage * (1.0 + FOX_FIRE_FOLLOW_UPS * FOX_FIRE_FOLLOW_UP_DAMAGE),
+            _ => damage,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::champions::fixtures::{assert_close, champion, player, ranks};
+
+    #[test]
+    fn ahri_orb_of_deception_returns_as_true_damage() {
+        let ahri = champion("Ahri");
+        let damage = ahri.raw_damage(Ability::Q, &player(9, 100.0), &ranks(5, 1, 1, 1));
+        assert_close(damage.magic, 185.0);
+        assert_close(damage.true_damage, 185.0);
+    }
+}
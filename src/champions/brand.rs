@@ -0,0 +1,92 @@
+use super::{definition::ChampionDefinition, is_spell, used_abilities, Ability, Champion};
+use crate::{
+    active_player,
+    dmg::{self, Damage, DamageType},
+    rotation::{Action, Step},
+    AbilityRanks,
+};
+
+// Ablaze burns for 3% of the target's max health, refreshed by every ability that hits
+const ABLAZE_BURN: f64 = 0.03;
+
+// The third stack of Ablaze detonates for 10% to 14% of the target's max health by level,
+// plus 2% per 100 AP
+const ABLAZE_STACKS: usize = 3;
+const DETONATION_MIN: f64 = 0.10;
+const DETONATION_MAX: f64 = 0.14;
+const DETONATION_PER_AP: f64 = 0.0002;
+
+// Pillar of Flame deals 25% more damage to targets that are ablaze
+const W_ABLAZE_BONUS: f64 = 0.25;
+
+#[derive(Debug)]
+pub struct Brand {
+    definition: ChampionDefinition,
+}
+
+impl Brand {
+    pub fn build(definition: ChampionDefinition) -> Brand {
+        Brand { definition }
+    }
+}
+
+impl Champion for Brand {
+    fn definition(&self) -> &ChampionDefinition {
+        &self.definition
+    }
+
+    fn passive_reads_previous(&self) -> bool {
+        true
+    }
+
+    fn passive_bonus(
+        &self,
+        previous: &[Step],
+        step: &Step,
+        active_player: &active_player::Root,
+        ability_ranks: &AbilityRanks,
+        target: &dmg::Target,
+    ) -> Damage {
+        let ability = match step.action {
+            Action::Ability(ability) if is_spell(ability) => ability,
+            _ => return Damage::default(),
+        };
+        let stacks = used_abilities(previous, ability_ranks)
+            .filter(|a| is_spell(*a))
+            .count();
+
+        let mut bonus = Damage::default();
+        if stacks == 0 {
+            bonus += Damage::new(ABLAZE_BURN * target.max_health, DamageType::Magic);
+        }
+        if stacks + 1 == ABLAZE_STACKS {
+            let level = (active_player.level - 1).clamp(0, 17) as f64;
+            let share = DETONATION_MIN
+                + (DETONATION_MAX - DETONATION_MIN) * level / 17.0
+                + DETONATION_PER_AP * active_player.champion_stats.ability_power;
+            bonus += Damage::new(share * target.max_health, DamageType::Magic);
+        }
+        if ability == Ability::W && stacks > 0 {
+            bonus += self.raw_damage(Ability::W, active_player, ability_ranks) * W_ABLAZE_BONUS;
+        }
+        bonus
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::champions::fixtures::{assert_close, burst, champion, player, ranks};
+
+    #[test]
+    fn brand_ablaze_burns_and_detonates() {
+        let brand = champion("Brand");
+        let (player, ranks) = (player(11, 100.0), ranks(1, 1, 1, 0));
+        // Sear and burn, empowered Pillar of Flame, then Conflagration and the detonation
+        let detonation = (0.10 + 0.04 * 10.0 / 17.0 + 0.02) * 2000.0;
+        let expected = 135.0 + 60.0 + 135.0 * 1.25 + 115.0 + detonation;
+        assert_close(
+            burst(brand.as_ref(), &player, &ranks, "QWE", 2000.0),
+            expected,
+        );
+    }
+}
@@ -0,0 +1,91 @@
+use super::{definition::ChampionDefinition, used_abilities, Ability, Champion};
+use crate::{
+    active_player,
+    dmg::{self, Damage, DamageType},
+    rotation::{Action, Step},
+    AbilityRanks,
+};
+
+// Twin Fang's base damage grows with champion level, its rank only sets the bonus damage
+// against poisoned targets held in the definition
+const E_BASE: f64 = 48.0;
+const E_PER_LEVEL: f64 = 4.0;
+const E_AP_RATIO: f64 = 0.1;
+
+#[derive(Debug)]
+pub struct Cassiopeia {
+    definition: ChampionDefinition,
+}
+
+impl Cassiopeia {
+    pub fn build(definition: ChampionDefinition) -> Cassiopeia {
+        Cassiopeia { definition }
+    }
+}
+
+impl Champion for Cassiopeia {
+    fn definition(&self) -> &ChampionDefinition {
+        &self.definition
+    }
+
+    fn passive_reads_previous(&self) -> bool {
+        true
+    }
+
+    fn raw_damage(
+        &self,
+        ability: Ability,
+        active_player: &active_player::Root,
+        ability_ranks: &AbilityRanks,
+    ) -> Damage {
+        match ability {
+            Ability::E if ability_ranks.e_rank > 0 => Damage::new(
+                E_BASE
+                    + E_PER_LEVEL * active_player.level as f64
+                    + E_AP_RATIO * active_player.champion_stats.ability_power,
+                DamageType::Magic,
+            ),
+            _ => self
+                .definition
+                .raw_damage(ability, active_player, ability_ranks),
+        }
+    }
+
+    // Noxious Blast and Miasma poison the target, which Twin Fang deals bonus damage to
+    fn passive_bonus(
+        &self,
+        previous: &[Step],
+        step: &Step,
+        active_player: &active_player::Root,
+        ability_ranks: &AbilityRanks,
+        _target: &dmg::Target,
+    ) -> Damage {
+        if step.action != Action::Ability(Ability::E) {
+            return Damage::default();
+        }
+        let poisoned = used_abilities(previous, ability_ranks)
+            .any(|ability| matches!(ability, Ability::Q | Ability::W));
+        if poisoned {
+            self.definition
+                .raw_damage(Ability::E, active_player, ability_ranks)
+        } else {
+            Damage::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::champions::fixtures::{assert_close, burst, champion, player, ranks};
+
+    #[test]
+    fn cassiopeia_twin_fang_deals_bonus_damage_to_poisoned_targets() {
+        let cassiopeia = champion("Cassiopeia");
+        let (player, ranks) = (player(10, 100.0), ranks(1, 0, 2, 0));
+        assert_close(burst(cassiopeia.as_ref(), &player, &ranks, "E", 0.0), 98.0);
+        assert_close(
+            burst(cassiopeia.as_ref(), &player, &ranks, "QE", 0.0),
+            363.0,
+        );
+    }
+}
@@ -0,0 +1,249 @@
+use std::{collections::HashMap, fmt, fs};
+
+use serde::Deserialize;
+
+use super::{Ability, Champion};
+use crate::{
+    active_player::{self, AbilityRanks},
+    dmg::{Damage, DamageType},
+    utils::stats,
+};
+
+// Declarative description of a champion's damage, loaded from a JSON file
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChampionDefinition {
+    pub name: String,
+    // Patch the definition's values were taken from, e.g. 12.13
+    #[serde(default)]
+    pub patch: Option<String>,
+    pub base_stats: BaseStats,
+    pub abilities: HashMap<Ability, AbilityDefinition>,
+}
+
+// Base stats used to split the active player's stats into base and bonus
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BaseStats {
+    pub attack_damage: f64,
+    pub attack_damage_per_level: f64,
+    pub health: f64,
+    pub health_per_level: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AbilityDefinition {
+    pub damage_type: DamageType,
+    // Share of mixed damage dealt as physical damage, the rest is dealt as magic damage
+    #[serde(default = "default_physical_share")]
+    pub physical_share: f64,
+    // Base damage per ability rank, or per champion level for the passive
+    pub base: Vec<f64>,
+    #[serde(default)]
+    pub ratios: Ratios,
+    // Increase of the damage against targets missing health
+    #[serde(default)]
+    pub missing_health_amp: Option<MissingHealthAmp>,
+    // Number of times a single cast hits its target
+    #[serde(default = "default_hits")]
+    pub hits: u32,
+    // Most enemies a single cast can hit
+    #[serde(default = "default_targets")]
+    pub targets: u32,
+    // Seconds spent casting the ability
+    #[serde(default = "default_cast_time")]
+    pub cast_time: f64,
+    // Cooldown in seconds per ability rank, empty if the ability has no cooldown data
+    #[serde(default)]
+    pub cooldown: Vec<f64>,
+    // Resource cost per ability rank, empty if the ability is free
+    #[serde(default)]
+    pub cost: Vec<f64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Ratios {
+    pub ap: f64,
+    pub bonus_ad: f64,
+    pub total_ad: f64,
+    pub max_health: f64,
+    pub bonus_health: f64,
+    pub missing_health: f64,
+    // Shares of the target's health, added once the target's health is known
+    pub target_max_health: f64,
+    pub target_current_health: f64,
+    pub target_missing_health: f64,
+}
+
+// Damage increases by per_missing for every share of health the target is missing, up to max
+#[derive(Debug, Clone, Deserialize)]
+pub struct MissingHealthAmp {
+    pub per_missing: f64,
+    pub max: f64,
+}
+
+fn default_hits() -> u32 {
+    1
+}
+
+fn default_targets() -> u32 {
+    1
+}
+
+fn default_cast_time() -> f64 {
+    0.25
+}
+
+fn default_physical_share() -> f64 {
+    0.5
+}
+
+impl ChampionDefinition {
+    pub fn rank(
+        &self,
+        ability: Ability,
+        active_player: &active_player::Root,
+        ranks: &AbilityRanks,
+    ) -> i64 {
+        match ability {
+            Ability::Q => ranks.q_rank,
+            Ability::W => ranks.w_rank,
+            Ability::E => ranks.e_rank,
+            Ability::R => ranks.r_rank,
+            Ability::Passive => active_player.level,
+            Ability::Attack => 1,
+        }
+    }
+
+    pub fn bonus_attack_damage(&self, active_player: &active_player::Root) -> f64 {
+        active_player.champion_stats.attack_damage
+            - stats::stat_at_level(
+                self.base_stats.attack_damage,
+                self.base_stats.attack_damage_per_level,
+                active_player.level,
+            )
+    }
+
+    pub fn bonus_health(&self, active_player: &active_player::Root) -> f64 {
+        active_player.champion_stats.max_health
+            - stats::stat_at_level(
+                self.base_stats.health,
+                self.base_stats.health_per_level,
+                active_player.level,
+            )
+    }
+
+    // Damage of a single cast of the ability before mitigation, from the base values and
+    // ratios of the definition
+    pub fn raw_damage(
+        &self,
+        ability: Ability,
+        active_player: &active_player::Root,
+        ability_ranks: &AbilityRanks,
+    ) -> Damage {
+        let definition = match self.abilities.get(&ability) {
+            Some(definition) => definition,
+            // Abilities without a definition deal no damage, except autos which deal total AD
+            None if ability == Ability::Attack => {
+                return Damage::new(
+                    active_player.champion_stats.attack_damage,
+                    DamageType::Physical,
+                )
+            }
+            None => return Damage::default(),
+        };
+        let rank = self.rank(ability, active_player, ability_ranks);
+        if rank < 1 || definition.base.is_empty() {
+            return Damage::default();
+        }
+        let base = definition.base[(rank as usize).min(definition.base.len()) - 1];
+        let champion_stats = &active_player.champion_stats;
+        let ratios = &definition.ratios;
+        let per_hit = base
+            + ratios.ap * champion_stats.ability_power
+            + ratios.bonus_ad * self.bonus_attack_damage(active_player)
+            + ratios.total_ad * champion_stats.attack_damage
+            + ratios.max_health * champion_stats.max_health
+            + ratios.bonus_health * self.bonus_health(active_player)
+            + ratios.missing_health * (champion_stats.max_health - champion_stats.current_health);
+        let total = per_hit * definition.hits as f64;
+        match definition.damage_type {
+            DamageType::Mixed => Damage::split(total, definition.physical_share),
+            damage_type => Damage::new(total, damage_type),
+        }
+    }
+}
+
+impl Champion for ChampionDefinition {
+    fn definition(&self) -> &ChampionDefinition {
+        self
+    }
+}
+
+// A champion definition file that couldn't be read or doesn't describe a champion
+#[derive(Debug)]
+pub struct DefinitionError {
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for DefinitionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid champion definition {}: {}",
+            self.path, self.message
+        )
+    }
+}
+
+impl std::error::Error for DefinitionError {}
+
+// Loads every champion definition in the directory, keyed by champion name
+pub fn load_definitions(dir: &str) -> Result<HashMap<String, ChampionDefinition>, DefinitionError> {
+    let error = |path: &dyn fmt::Display, err: &dyn fmt::Display| DefinitionError {
+        path: path.to_string(),
+        message: err.to_string(),
+    };
+    let mut definitions = HashMap::new();
+    for entry in fs::read_dir(dir).map_err(|err| error(&dir, &err))? {
+        let path = entry.map_err(|err| error(&dir, &err))?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let text = fs::read_to_string(&path).map_err(|err| error(&path.display(), &err))?;
+        let definition: ChampionDefinition =
+            serde_json::from_str(&text).map_err(|err| error(&path.display(), &err))?;
+        debug!("Loaded champion definition for {}", definition.name);
+        definitions.insert(definition.name.clone(), definition);
+    }
+    info!(
+        "Loaded {} champion definitions from {}",
+        definitions.len(),
+        dir
+    );
+    Ok(definitions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::champions::fixtures::{assert_close, champion, player, ranks};
+
+    #[test]
+    fn annie_disintegrate_matches_tooltip() {
+        let annie = champion("Annie");
+        let damage = annie.raw_damage(Ability::Q, &player(7, 100.0), &ranks(3, 1, 1, 1));
+        assert_close(damage.magic, 230.0);
+    }
+
+    #[test]
+    fn malformed_definitions_are_reported_with_their_path() {
+        let dir = std::env::temp_dir().join(format!("lolburst-definitions-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("broken.json"), "{ \"name\": \"Broken\" ").unwrap();
+        let err = load_definitions(dir.to_str().unwrap()).unwrap_err();
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(err.path.ends_with("broken.json"), "{}", err);
+        assert!(load_definitions("./resources/missing").is_err());
+    }
+}
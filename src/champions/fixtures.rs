@@ -0,0 +1,94 @@
+use super::{definition, match_champion, Champion};
+use crate::{
+    active_player::{self, AbilityRanks},
+    dmg,
+    loadout::{CritMode, Loadout},
+    runes::Runes,
+    summoners::SummonerSpell,
+};
+
+// Shared setup for the champion tests: a player without items or summoner spells bursting a
+// target without resistances or shields
+
+pub fn champion(name: &str) -> Box<dyn Champion> {
+    let definitions = definition::load_definitions("./resources/champions").unwrap();
+    match_champion(name, &definitions).expect("Champion has no definition")
+}
+
+pub fn player(level: i64, ability_power: f64) -> active_player::Root {
+    let mut player = active_player::Root {
+        level,
+        ..Default::default()
+    };
+    player.champion_stats.ability_power = ability_power;
+    player
+}
+
+pub fn ranks(q: i64, w: i64, e: i64, r: i64) -> AbilityRanks {
+    AbilityRanks::new(q, w, e, r)
+}
+
+pub fn loadout(runes: Runes) -> Loadout {
+    Loadout {
+        item_effects: Vec::new(),
+        summoner_spells: [SummonerSpell::Other; 2],
+        runes,
+        crit_mode: CritMode::Expected,
+    }
+}
+
+pub fn target(max_health: f64, current_health: f64) -> dmg::Target {
+    dmg::Target {
+        resistance: dmg::Resistance::new(0.0, 0.0, 0.0, 0.0),
+        max_health,
+        current_health,
+        shield: 0.0,
+        magic_shield: 0.0,
+        spell_shield: false,
+        position: 0,
+    }
+}
+
+// Damage of the rotation against a target at full health without resistances, items or runes
+pub fn burst(
+    champion: &dyn Champion,
+    player: &active_player::Root,
+    ability_ranks: &AbilityRanks,
+    rotation: &str,
+    max_health: f64,
+) -> f64 {
+    burst_at(
+        champion,
+        player,
+        ability_ranks,
+        rotation,
+        max_health,
+        max_health,
+    )
+}
+
+pub fn burst_at(
+    champion: &dyn Champion,
+    player: &active_player::Root,
+    ability_ranks: &AbilityRanks,
+    rotation: &str,
+    max_health: f64,
+    current_health: f64,
+) -> f64 {
+    champion.calculate_damage(
+        player,
+        &loadout(Runes::default()),
+        &rotation.parse().expect("Invalid rotation"),
+        ability_ranks,
+        &target(max_health, current_health),
+    )
+}
+
+pub fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 1e-6,
+        "expected {}, got {}",
+        expected,
+        actual
+    );
+}
@@ -0,0 +1,63 @@
+use super::{definition::ChampionDefinition, Ability, Champion};
+use crate::{
+    active_player,
+    dmg::{self, Damage, DamageType},
+    AbilityRanks,
+};
+
+// Demacian Justice deals true damage scaling with the target's missing health per rank
+const R_MISSING_HEALTH_RATIO: [f64; 3] = [0.25, 0.3, 0.35];
+
+#[derive(Debug)]
+pub struct Garen {
+    definition: ChampionDefinition,
+}
+
+impl Garen {
+    pub fn build(definition: ChampionDefinition) -> Garen {
+        Garen { definition }
+    }
+}
+
+impl Champion for Garen {
+    fn definition(&self) -> &ChampionDefinition {
+        &self.definition
+    }
+
+    fn target_damage(
+        &self,
+        ability: Ability,
+        _active_player: &active_player::Root,
+        ability_ranks: &AbilityRanks,
+        target: &dmg::Target,
+    ) -> Damage {
+        match ability {
+            Ability::R if ability_ranks.r_rank > 0 => {
+                let rank = (ability_ranks.r_rank as usize).min(R_MISSING_HEALTH_RATIO.len());
+                Damage::new(
+                    R_MISSING_HEALTH_RATIO[rank - 1] * target.missing_health(),
+                    DamageType::True,
+                )
+            }
+            _ => Damage::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::champions::fixtures::{assert_close, burst, burst_at, champion, player, ranks};
+
+    #[test]
+    fn garen_demacian_justice_scales_with_health_lost_during_the_burst() {
+        let garen = champion("Garen");
+        let (player, ranks) = (player(6, 0.0), ranks(1, 0, 0, 1));
+        assert_close(
+            burst_at(garen.as_ref(), &player, &ranks, "R", 2000.0, 1000.0),
+            400.0,
+        );
+        let q = burst(garen.as_ref(), &player, &ranks, "Q", 2000.0);
+        let r = burst(garen.as_ref(), &player, &ranks, "QR", 2000.0) - q;
+        assert_close(r, 150.0 + 0.25 * q);
+    }
+}
@@ -0,0 +1,69 @@
+use super::{after_last, definition::ChampionDefinition, used_abilities, Ability, Champion};
+use crate::{
+    active_player,
+    dmg::{self, Damage},
+    rotation::{Action, Step},
+    AbilityRanks,
+};
+
+#[derive(Debug)]
+pub struct LeBlanc {
+    definition: ChampionDefinition,
+}
+
+impl LeBlanc {
+    pub fn build(definition: ChampionDefinition) -> LeBlanc {
+        LeBlanc { definition }
+    }
+}
+
+// Abilities that detonate Sigil of Malice
+fn detonates_sigil(ability: Ability) -> bool {
+    matches!(ability, Ability::W | Ability::E | Ability::R)
+}
+
+impl Champion for LeBlanc {
+    fn definition(&self) -> &ChampionDefinition {
+        &self.definition
+    }
+
+    fn passive_reads_previous(&self) -> bool {
+        true
+    }
+
+    // Sigil of Malice marks the target, and the next ability to hit them detonates the mark
+    // for Sigil of Malice's damage again
+    fn passive_bonus(
+        &self,
+        previous: &[Step],
+        step: &Step,
+        active_player: &active_player::Root,
+        ability_ranks: &AbilityRanks,
+        _target: &dmg::Target,
+    ) -> Damage {
+        match step.action {
+            Action::Ability(ability) if detonates_sigil(ability) => {}
+            _ => return Damage::default(),
+        }
+        let marked = used_abilities(after_last(previous, detonates_sigil), ability_ranks)
+            .any(|ability| ability == Ability::Q);
+        if marked {
+            self.raw_damage(Ability::Q, active_player, ability_ranks)
+        } else {
+            Damage::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::champions::fixtures::{assert_close, burst, champion, player, ranks};
+
+    #[test]
+    fn leblanc_sigil_of_malice_detonates_on_the_next_ability() {
+        let leblanc = champion("LeBlanc");
+        let (player, ranks) = (player(3, 100.0), ranks(1, 1, 1, 0));
+        assert_close(burst(leblanc.as_ref(), &player, &ranks, "QW", 0.0), 345.0);
+        assert_close(burst(leblanc.as_ref(), &player, &ranks, "WQ", 0.0), 240.0);
+    }
+}
@@ -0,0 +1,73 @@
+use super::{definition::ChampionDefinition, used_abilities, Ability, Champion};
+use crate::{
+    active_player,
+    dmg::{self, Damage},
+    rotation::{Action, Step},
+    AbilityRanks,
+};
+
+#[derive(Debug)]
+pub struct Lux {
+    definition: ChampionDefinition,
+}
+
+impl Lux {
+    pub fn build(definition: ChampionDefinition) -> Lux {
+        Lux { definition }
+    }
+}
+
+impl Champion for Lux {
+    fn definition(&self) -> &ChampionDefinition {
+        &self.definition
+    }
+
+    fn passive_reads_previous(&self) -> bool {
+        true
+    }
+
+    // Illumination: damaging abilities mark the target, and the next auto attack or Final
+    // Spark detonates the mark. Final Spark marks the target again after detonating.
+    fn passive_bonus(
+        &self,
+        previous: &[Step],
+        step: &Step,
+        active_player: &active_player::Root,
+        ability_ranks: &AbilityRanks,
+        _target: &dmg::Target,
+    ) -> Damage {
+        if !matches!(
+            step.action,
+            Action::Ability(Ability::Attack) | Action::Ability(Ability::R)
+        ) {
+            return Damage::default();
+        }
+        let mut marked = false;
+        for ability in used_abilities(previous, ability_ranks) {
+            match ability {
+                Ability::Q | Ability::E | Ability::R => marked = true,
+                Ability::Attack => marked = false,
+                _ => {}
+            }
+        }
+        if marked {
+            self.raw_damage(Ability::Passive, active_player, ability_ranks)
+        } else {
+            Damage::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::champions::fixtures::{assert_close, burst, champion, player, ranks};
+
+    #[test]
+    fn lux_illumination_detonates_on_auto_attack() {
+        let lux = champion("Lux");
+        let (player, ranks) = (player(9, 100.0), ranks(1, 0, 1, 0));
+        assert_close(burst(lux.as_ref(), &player, &ranks, "A", 0.0), 0.0);
+        assert_close(burst(lux.as_ref(), &player, &ranks, "EA", 0.0), 260.0);
+        assert_close(burst(lux.as_ref(), &player, &ranks, "EAA", 0.0), 260.0);
+    }
+}
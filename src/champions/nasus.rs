@@ -0,0 +1,59 @@
+use super::{definition::ChampionDefinition, Ability, Champion};
+use crate::{
+    active_player,
+    dmg::{Damage, DamageType},
+    stacks::StackSource,
+    AbilityRanks,
+};
+
+#[derive(Debug)]
+pub struct Nasus {
+    definition: ChampionDefinition,
+    // Siphoning Strike stacks, which don't show up in any stat
+    stacks: u32,
+}
+
+impl Nasus {
+    pub fn build(definition: ChampionDefinition) -> Nasus {
+        Nasus {
+            definition,
+            stacks: 0,
+        }
+    }
+}
+
+impl Champion for Nasus {
+    fn definition(&self) -> &ChampionDefinition {
+        &self.definition
+    }
+
+    // Siphoning Strike deals one bonus physical damage per stack
+    fn raw_damage(
+        &self,
+        ability: Ability,
+        active_player: &active_player::Root,
+        ability_ranks: &AbilityRanks,
+    ) -> Damage {
+        let damage = self
+            .definition
+            .raw_damage(ability, active_player, ability_ranks);
+        match ability {
+            Ability::Q if ability_ranks.q_rank > 0 => {
+                damage + Damage::new(self.stacks as f64, DamageType::Physical)
+            }
+            _ => damage,
+        }
+    }
+
+    fn stack_source(&self) -> Option<StackSource> {
+        Some(StackSource::Manual)
+    }
+
+    fn set_stacks(&mut self, stacks: u32) {
+        self.stacks = stacks;
+    }
+
+    fn scales_with_stacks(&self) -> bool {
+        true
+    }
+}
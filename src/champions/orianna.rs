@@ -1,124 +1,80 @@
-use crate::{active_player, dmg, AbilityRanks};
+use super::{definition::ChampionDefinition, Ability, Champion};
+use crate::{active_player, dmg::Damage, AbilityRanks};
+
+// Clockwork Windup deals 20% more damage per consecutive hit on the same target, stacking twice
+const PASSIVE_STACK_BONUS: f64 = 0.2;
+const PASSIVE_MAX_STACKS: u32 = 2;
+
+// Command: Attack deals 10% less damage to each enemy hit after the first, down to 40%
+const Q_FALLOFF: f64 = 0.1;
+const Q_MIN_MULTIPLIER: f64 = 0.4;
 
 #[derive(Debug)]
 pub struct Orianna {
-    pub name: String,
-    stats: Stats,
+    definition: ChampionDefinition,
 }
 
 impl Orianna {
-    fn new(name: String, stats: Stats) -> Self {
-        Orianna { name, stats }
+    pub fn build(definition: ChampionDefinition) -> Orianna {
+        Orianna { definition }
     }
+}
 
-    pub fn build() -> Orianna {
-        Orianna::new(
-            String::from("Orianna"),
-            Stats::new(
-                vec![0.0, 60.0, 90.0, 120.0, 150.0, 180.0, 0.5],
-                vec![0.0, 60.0, 105.0, 150.0, 195.0, 240.0, 0.7],
-                vec![0.0, 60.0, 90.0, 120.0, 150.0, 180.0, 0.3],
-                vec![0.0, 200.0, 275.0, 350.0, 0.8],
-                vec![10.0, 18.0, 26.0, 34.0, 42.0, 50.0, 0.15, 1.2],
-            ),
-        )
+impl Champion for Orianna {
+    fn definition(&self) -> &ChampionDefinition {
+        &self.definition
     }
 
-    pub fn calculate_rd(
+    fn passive_damage(
         &self,
         active_player: &active_player::Root,
-        abilityranks: &AbilityRanks,
-    ) -> RawDamage {
-        let q_rank = abilityranks.q_rank;
-        let w_rank = abilityranks.w_rank;
-        let e_rank = abilityranks.e_rank;
-        let r_rank = abilityranks.r_rank;
-        let ap = active_player.champion_stats.ability_power;
-        let ad = active_player.champion_stats.attack_damage;
-        let level = active_player.level;
-        RawDamage {
-            q: (self.stats.q_dmg[q_rank as usize]) + (self.stats.q_dmg[6] * ap),
-            w: (self.stats.w_dmg[w_rank as usize]) + (self.stats.w_dmg[6] * ap),
-            e: (self.stats.e_dmg[e_rank as usize]) + (self.stats.e_dmg[6] * ap),
-            r: (self.stats.r_dmg[r_rank as usize]) + (self.stats.r_dmg[4] * ap),
-            p: self.stats.p_dmg[(((level - 1) / 3) as f64).floor() as usize],
-            aa: ad,
-        }
+        ability_ranks: &AbilityRanks,
+        procs: u32,
+    ) -> Damage {
+        let stacks = procs.min(PASSIVE_MAX_STACKS) as f64;
+        self.definition
+            .raw_damage(Ability::Passive, active_player, ability_ranks)
+            * (1.0 + PASSIVE_STACK_BONUS * stacks)
     }
 
-    pub fn calculate_damage(
-        &self,
-        active_player: &active_player::Root,
-        ability: &str,
-        abilityranks: &AbilityRanks,
-        resistance: dmg::Resistance,
-    ) -> f64 {
-        let mut dmg = Vec::new();
-        let raw_damage = self.calculate_rd(active_player, abilityranks);
-
-        for i in ability.chars() {
-            match i {
-                'Q' => dmg.push(dmg::calculate_mitigation(
-                    raw_damage.q,
-                    resistance.magic_resist,
-                )),
-                'W' => dmg.push(dmg::calculate_mitigation(
-                    raw_damage.w,
-                    resistance.magic_resist,
-                )),
-                'E' => dmg.push(dmg::calculate_mitigation(
-                    raw_damage.e,
-                    resistance.magic_resist,
-                )),
-                'R' => dmg.push(dmg::calculate_mitigation(
-                    raw_damage.r,
-                    resistance.magic_resist,
-                )),
-                'P' => dmg.push(dmg::calculate_mitigation(
-                    raw_damage.p,
-                    resistance.magic_resist,
-                )),
-                'A' => dmg.push(dmg::calculate_mitigation(raw_damage.aa, resistance.armor)),
-                _ => println!("Invalid ability"),
-            }
+    fn target_multiplier(&self, ability: Ability, position: u32) -> f64 {
+        match ability {
+            Ability::Q => (1.0 - Q_FALLOFF * position as f64).max(Q_MIN_MULTIPLIER),
+            _ => 1.0,
         }
+    }
 
-        dmg.iter().sum()
+    // Clockwork Windup is an on-hit effect of Orianna's auto attacks
+    fn passive_on_attack(&self) -> bool {
+        true
     }
 }
 
-#[derive(Debug)]
-struct Stats {
-    q_dmg: Vec<f64>,
-    w_dmg: Vec<f64>,
-    e_dmg: Vec<f64>,
-    r_dmg: Vec<f64>,
-    p_dmg: Vec<f64>,
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::champions::fixtures::{assert_close, champion, player, ranks};
 
-impl Stats {
-    fn new(
-        q_dmg: Vec<f64>,
-        w_dmg: Vec<f64>,
-        e_dmg: Vec<f64>,
-        r_dmg: Vec<f64>,
-        p_dmg: Vec<f64>,
-    ) -> Self {
-        Stats {
-            q_dmg,
-            w_dmg,
-            e_dmg,
-            r_dmg,
-            p_dmg,
+    #[test]
+    fn orianna_clockwork_windup_stacks_twice() {
+        let orianna = champion("Orianna");
+        let (player, ranks) = (player(6, 100.0), ranks(1, 0, 0, 0));
+        let first = orianna.passive_damage(&player, &ranks, 0).total();
+        assert!(first > 0.0);
+        for (procs, multiplier) in [(1, 1.2), (2, 1.4), (3, 1.4), (10, 1.4)] {
+            assert_close(
+                orianna.passive_damage(&player, &ranks, procs).total(),
+                first * multiplier,
+            );
         }
     }
-}
 
-pub struct RawDamage {
-    q: f64,
-    w: f64,
-    e: f64,
-    r: f64,
-    p: f64,
-    aa: f64,
+    #[test]
+    fn orianna_command_attack_falls_off_per_enemy_hit() {
+        let orianna = champion("Orianna");
+        for (position, multiplier) in [(0, 1.0), (1, 0.9), (2, 0.8), (5, 0.5), (6, 0.4), (9, 0.4)] {
+            assert_close(orianna.target_multiplier(Ability::Q, position), multiplier);
+        }
+        assert_close(orianna.target_multiplier(Ability::R, 3), 1.0);
+    }
 }
@@ -0,0 +1,91 @@
+use super::{definition::ChampionDefinition, Ability, Champion};
+use crate::{
+    active_player,
+    dmg::{Damage, DamageType},
+    AbilityRanks,
+};
+
+// Death from Below executes champions below a threshold growing with Pyke's level from 6 on,
+// and deals half the threshold as physical damage to champions above it
+const R_THRESHOLD: [f64; 13] = [
+    250.0, 290.0, 330.0, 370.0, 400.0, 430.0, 450.0, 470.0, 490.0, 510.0, 530.0, 540.0, 550.0,
+];
+const R_THRESHOLD_BONUS_AD_RATIO: f64 = 0.8;
+const R_THRESHOLD_LETHALITY_RATIO: f64 = 1.5;
+const R_DAMAGE_SHARE: f64 = 0.5;
+const R_FIRST_LEVEL: usize = 6;
+
+#[derive(Debug)]
+pub struct Pyke {
+    definition: ChampionDefinition,
+}
+
+impl Pyke {
+    pub fn build(definition: ChampionDefinition) -> Pyke {
+        Pyke { definition }
+    }
+
+    fn r_threshold(&self, active_player: &active_player::Root) -> f64 {
+        let level = (active_player.level.max(R_FIRST_LEVEL as i64) as usize - R_FIRST_LEVEL)
+            .min(R_THRESHOLD.len() - 1);
+        R_THRESHOLD[level]
+            + R_THRESHOLD_BONUS_AD_RATIO * self.definition.bonus_attack_damage(active_player)
+            + R_THRESHOLD_LETHALITY_RATIO * active_player.champion_stats.physical_lethality
+    }
+}
+
+impl Champion for Pyke {
+    fn definition(&self) -> &ChampionDefinition {
+        &self.definition
+    }
+
+    fn raw_damage(
+        &self,
+        ability: Ability,
+        active_player: &active_player::Root,
+        ability_ranks: &AbilityRanks,
+    ) -> Damage {
+        match ability {
+            Ability::R if ability_ranks.r_rank > 0 => Damage::new(
+                R_DAMAGE_SHARE * self.r_threshold(active_player),
+                DamageType::Physical,
+            ),
+            _ => self
+                .definition
+                .raw_damage(ability, active_player, ability_ranks),
+        }
+    }
+
+    fn execute_threshold(
+        &self,
+        ability: Ability,
+        active_player: &active_player::Root,
+        ability_ranks: &AbilityRanks,
+    ) -> Option<f64> {
+        match ability {
+            Ability::R if ability_ranks.r_rank > 0 => Some(self.r_threshold(active_player)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::champions::fixtures::{assert_close, burst_at, champion, player, ranks};
+
+    #[test]
+    fn pyke_death_from_below_executes_below_threshold() {
+        let pyke = champion("Pyke");
+        let (mut player, ranks) = (player(6, 0.0), ranks(1, 0, 0, 1));
+        // Base attack damage at level 6, so the threshold has no bonus attack damage
+        player.champion_stats.attack_damage = 69.9;
+        assert_close(
+            burst_at(pyke.as_ref(), &player, &ranks, "R", 2000.0, 240.0),
+            240.0,
+        );
+        assert_close(
+            burst_at(pyke.as_ref(), &player, &ranks, "R", 2000.0, 1000.0),
+            125.0,
+        );
+    }
+}
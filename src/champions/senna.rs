@@ -0,0 +1,27 @@
+use super::{definition::ChampionDefinition, Champion};
+use crate::stacks::StackSource;
+
+// Absolution grants attack damage per soul collected, which the Live Client already counts in
+// Senna's attack damage
+const PASSIVE_ATTACK_DAMAGE_PER_SOUL: f64 = 0.75;
+
+#[derive(Debug)]
+pub struct Senna {
+    definition: ChampionDefinition,
+}
+
+impl Senna {
+    pub fn build(definition: ChampionDefinition) -> Senna {
+        Senna { definition }
+    }
+}
+
+impl Champion for Senna {
+    fn definition(&self) -> &ChampionDefinition {
+        &self.definition
+    }
+
+    fn stack_source(&self) -> Option<StackSource> {
+        Some(StackSource::AttackDamage(PASSIVE_ATTACK_DAMAGE_PER_SOUL))
+    }
+}
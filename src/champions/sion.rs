@@ -0,0 +1,28 @@
+use super::{definition::ChampionDefinition, Champion};
+use crate::stacks::StackSource;
+
+// Soul Furnace grants max health per unit killed while it's ready, which the Live Client
+// already counts in Sion's health. Kills of champions and large units grant more, so they
+// count as several stacks.
+const W_HEALTH_PER_STACK: f64 = 4.0;
+
+#[derive(Debug)]
+pub struct Sion {
+    definition: ChampionDefinition,
+}
+
+impl Sion {
+    pub fn build(definition: ChampionDefinition) -> Sion {
+        Sion { definition }
+    }
+}
+
+impl Champion for Sion {
+    fn definition(&self) -> &ChampionDefinition {
+        &self.definition
+    }
+
+    fn stack_source(&self) -> Option<StackSource> {
+        Some(StackSource::Health(W_HEALTH_PER_STACK))
+    }
+}
@@ -0,0 +1,65 @@
+use super::{definition::ChampionDefinition, Ability, Champion};
+use crate::{
+    active_player,
+    dmg::{Damage, DamageType},
+    stacks::StackSource,
+    AbilityRanks,
+};
+
+// Dragon Practice adds bonus physical damage per stack to Super Scorcher Breath
+const Q_DAMAGE_PER_STACK: f64 = 0.3;
+
+#[derive(Debug)]
+pub struct Smolder {
+    definition: ChampionDefinition,
+    // Dragon Practice stacks, which don't show up in any stat
+    stacks: u32,
+}
+
+impl Smolder {
+    pub fn build(definition: ChampionDefinition) -> Smolder {
+        Smolder {
+            definition,
+            stacks: 0,
+        }
+    }
+}
+
+impl Champion for Smolder {
+    fn definition(&self) -> &ChampionDefinition {
+        &self.definition
+    }
+
+    fn raw_damage(
+        &self,
+        ability: Ability,
+        active_player: &active_player::Root,
+        ability_ranks: &AbilityRanks,
+    ) -> Damage {
+        let damage = self
+            .definition
+            .raw_damage(ability, active_player, ability_ranks);
+        match ability {
+            Ability::Q if ability_ranks.q_rank > 0 => {
+                damage
+                    + Damage::new(
+                        Q_DAMAGE_PER_STACK * self.stacks as f64,
+                        DamageType::Physical,
+                    )
+            }
+            _ => damage,
+        }
+    }
+
+    fn stack_source(&self) -> Option<StackSource> {
+        Some(StackSource::Manual)
+    }
+
+    fn set_stacks(&mut self, stacks: u32) {
+        self.stacks = stacks;
+    }
+
+    fn scales_with_stacks(&self) -> bool {
+        true
+    }
+}
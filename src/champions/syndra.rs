@@ -0,0 +1,98 @@
+use super::{definition::ChampionDefinition, used_abilities, Ability, Champion};
+use crate::{
+    active_player,
+    dmg::{self, Damage, DamageType},
+    rotation::{Action, Step},
+    AbilityRanks,
+};
+
+// Unleashed Power throws the spheres Syndra starts with plus every Dark Sphere still out
+const BASE_SPHERES: usize = 3;
+const MAX_SPHERES: usize = 7;
+
+// Transcendent: Dark Sphere deals 25% more damage to champions at max rank, and Force of
+// Will deals 20% of its damage as bonus true damage at max rank
+const Q_MAX_RANK_BONUS: f64 = 0.25;
+const W_MAX_RANK_TRUE_DAMAGE: f64 = 0.2;
+
+#[derive(Debug)]
+pub struct Syndra {
+    definition: ChampionDefinition,
+}
+
+impl Syndra {
+    pub fn build(definition: ChampionDefinition) -> Syndra {
+        Syndra { definition }
+    }
+}
+
+impl Champion for Syndra {
+    fn definition(&self) -> &ChampionDefinition {
+        &self.definition
+    }
+
+    fn passive_reads_previous(&self) -> bool {
+        true
+    }
+
+    fn raw_damage(
+        &self,
+        ability: Ability,
+        active_player: &active_player::Root,
+        ability_ranks: &AbilityRanks,
+    ) -> Damage {
+        let damage = self
+            .definition
+            .raw_damage(ability, active_player, ability_ranks);
+        match ability {
+            Ability::Q if ability_ranks.q_rank >= 5 => damage * (1.0 + Q_MAX_RANK_BONUS),
+            Ability::W if ability_ranks.w_rank >= 5 => {
+                damage + Damage::new(damage.total() * W_MAX_RANK_TRUE_DAMAGE, DamageType::True)
+            }
+            _ => damage,
+        }
+    }
+
+    // Every Dark Sphere cast before Unleashed Power adds another sphere to it
+    fn passive_bonus(
+        &self,
+        previous: &[Step],
+        step: &Step,
+        active_player: &active_player::Root,
+        ability_ranks: &AbilityRanks,
+        _target: &dmg::Target,
+    ) -> Damage {
+        if step.action != Action::Ability(Ability::R) {
+            return Damage::default();
+        }
+        let spheres = used_abilities(previous, ability_ranks)
+            .filter(|ability| *ability == Ability::Q)
+            .count()
+            .min(MAX_SPHERES - BASE_SPHERES);
+        self.raw_damage(Ability::R, active_player, ability_ranks)
+            * (spheres as f64 / BASE_SPHERES as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::champions::fixtures::{assert_close, burst, champion, player, ranks};
+
+    #[test]
+    fn syndra_dark_sphere_is_stronger_at_max_rank() {
+        let syndra = champion("Syndra");
+        let damage = syndra.raw_damage(Ability::Q, &player(9, 100.0), &ranks(5, 1, 1, 1));
+        assert_close(damage.magic, 343.75);
+    }
+
+    #[test]
+    fn syndra_unleashed_power_throws_every_sphere() {
+        let syndra = champion("Syndra");
+        let (player, ranks) = (player(6, 100.0), ranks(1, 1, 1, 1));
+        assert_close(burst(syndra.as_ref(), &player, &ranks, "R", 0.0), 321.0);
+        let spheres = burst(syndra.as_ref(), &player, &ranks, "QQR", 0.0)
+            - burst(syndra.as_ref(), &player, &ranks, "QQ", 0.0);
+        assert_close(spheres, 535.0);
+    }
+}
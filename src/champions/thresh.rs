@@ -0,0 +1,72 @@
+use super::{definition::ChampionDefinition, Ability, Champion};
+use crate::{
+    active_player,
+    dmg::{Damage, DamageType},
+    stacks::StackSource,
+    AbilityRanks,
+};
+
+// Damnation grants ability power per soul collected, which the Live Client already counts
+const PASSIVE_ABILITY_POWER_PER_SOUL: f64 = 1.0;
+
+// Flay's passive adds magic damage to auto attacks equal to the souls collected plus a share
+// of attack damage, assuming the attack is fully charged
+const E_PASSIVE_AD_RATIO: [f64; 5] = [0.8, 1.1, 1.4, 1.7, 2.0];
+
+#[derive(Debug)]
+pub struct Thresh {
+    definition: ChampionDefinition,
+    // Souls collected by Damnation
+    stacks: u32,
+}
+
+impl Thresh {
+    pub fn build(definition: ChampionDefinition) -> Thresh {
+        Thresh {
+            definition,
+            stacks: 0,
+        }
+    }
+}
+
+impl Champion for Thresh {
+    fn definition(&self) -> &ChampionDefinition {
+        &self.definition
+    }
+
+    fn raw_damage(
+        &self,
+        ability: Ability,
+        active_player: &active_player::Root,
+        ability_ranks: &AbilityRanks,
+    ) -> Damage {
+        let damage = self
+            .definition
+            .raw_damage(ability, active_player, ability_ranks);
+        match ability {
+            Ability::Attack if ability_ranks.e_rank > 0 => {
+                let rank = (ability_ranks.e_rank as usize).min(E_PASSIVE_AD_RATIO.len());
+                damage
+                    + Damage::new(
+                        self.stacks as f64
+                            + E_PASSIVE_AD_RATIO[rank - 1]
+                                * active_player.champion_stats.attack_damage,
+                        DamageType::Magic,
+                    )
+            }
+            _ => damage,
+        }
+    }
+
+    fn stack_source(&self) -> Option<StackSource> {
+        Some(StackSource::AbilityPower(PASSIVE_ABILITY_POWER_PER_SOUL))
+    }
+
+    fn set_stacks(&mut self, stacks: u32) {
+        self.stacks = stacks;
+    }
+
+    fn scales_with_stacks(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,56 @@
+use super::{definition::ChampionDefinition, Champion};
+use crate::stacks::StackSource;
+
+// Phenomenal Evil grants ability power per stack, which the Live Client already counts in
+// Veigar's ability power, so stacks only need to be counted and not added to the damage
+const PASSIVE_ABILITY_POWER_PER_STACK: f64 = 1.0;
+
+#[derive(Debug)]
+pub struct Veigar {
+    definition: ChampionDefinition,
+}
+
+impl Veigar {
+    pub fn build(definition: ChampionDefinition) -> Veigar {
+        Veigar { definition }
+    }
+}
+
+impl Champion for Veigar {
+    fn definition(&self) -> &ChampionDefinition {
+        &self.definition
+    }
+
+    fn stack_source(&self) -> Option<StackSource> {
+        Some(StackSource::AbilityPower(PASSIVE_ABILITY_POWER_PER_STACK))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::champions::{
+        fixtures::{assert_close, burst_at, champion, player, ranks},
+        Ability,
+    };
+
+    #[test]
+    fn veigar_primordial_burst_matches_tooltip() {
+        let veigar = champion("Veigar");
+        let damage = veigar.raw_damage(Ability::R, &player(16, 200.0), &ranks(5, 5, 3, 3));
+        assert_close(damage.magic, 475.0);
+    }
+
+    #[test]
+    fn veigar_primordial_burst_grows_with_missing_health() {
+        let veigar = champion("Veigar");
+        let (player, ranks) = (player(16, 200.0), ranks(5, 5, 3, 3));
+        assert_close(
+            burst_at(veigar.as_ref(), &player, &ranks, "R", 2000.0, 1500.0),
+            653.125,
+        );
+        assert_close(
+            burst_at(veigar.as_ref(), &player, &ranks, "R", 2000.0, 500.0),
+            950.0,
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use super::{definition::ChampionDefinition, is_spell, used_abilities, Champion};
+use crate::{
+    active_player,
+    dmg::{self, Damage, DamageType},
+    rotation::{Action, Step},
+    AbilityRanks,
+};
+
+// Organic Deconstruction: every third ability hit deals 25 + 8 per level true damage
+const RESEARCH_STACKS: usize = 3;
+const RESEARCH_BASE: f64 = 25.0;
+const RESEARCH_PER_LEVEL: f64 = 8.0;
+
+#[derive(Debug)]
+pub struct VelKoz {
+    definition: ChampionDefinition,
+}
+
+impl VelKoz {
+    pub fn build(definition: ChampionDefinition) -> VelKoz {
+        VelKoz { definition }
+    }
+}
+
+impl Champion for VelKoz {
+    fn definition(&self) -> &ChampionDefinition {
+        &self.definition
+    }
+
+    fn passive_reads_previous(&self) -> bool {
+        true
+    }
+
+    fn passive_bonus(
+        &self,
+        previous: &[Step],
+        step: &Step,
+        active_player: &active_player::Root,
+        ability_ranks: &AbilityRanks,
+        _target: &dmg::Target,
+    ) -> Damage {
+        match step.action {
+            Action::Ability(ability) if is_spell(ability) => {}
+            _ => return Damage::default(),
+        }
+        let stacks = used_abilities(previous, ability_ranks)
+            .filter(|ability| is_spell(*ability))
+            .count()
+            + 1;
+        if stacks % RESEARCH_STACKS == 0 {
+            Damage::new(
+                RESEARCH_BASE + RESEARCH_PER_LEVEL * active_player.level as f64,
+                DamageType::True,
+            )
+        } else {
+            Damage::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::champions::fixtures::{assert_close, burst, champion, player, ranks};
+
+    #[test]
+    fn velkoz_research_deals_true_damage_on_the_third_hit() {
+        let velkoz = champion("Vel'Koz");
+        let (player, ranks) = (player(6, 0.0), ranks(1, 1, 1, 1));
+        assert_close(burst(velkoz.as_ref(), &player, &ranks, "QWE", 0.0), 298.0);
+    }
+}
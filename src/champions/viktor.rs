@@ -0,0 +1,72 @@
+use super::{after_last, definition::ChampionDefinition, used_abilities, Ability, Champion};
+use crate::{
+    active_player,
+    dmg::{self, Damage, DamageType},
+    rotation::{Action, Step},
+    AbilityRanks,
+};
+
+// Siphon Power charges Viktor's next auto attack with bonus magic damage
+const Q_DISCHARGE: [f64; 5] = [20.0, 45.0, 70.0, 95.0, 120.0];
+const Q_DISCHARGE_AP_RATIO: f64 = 0.6;
+
+#[derive(Debug)]
+pub struct Viktor {
+    definition: ChampionDefinition,
+}
+
+impl Viktor {
+    pub fn build(definition: ChampionDefinition) -> Viktor {
+        Viktor { definition }
+    }
+}
+
+impl Champion for Viktor {
+    fn definition(&self) -> &ChampionDefinition {
+        &self.definition
+    }
+
+    fn passive_reads_previous(&self) -> bool {
+        true
+    }
+
+    fn passive_bonus(
+        &self,
+        previous: &[Step],
+        step: &Step,
+        active_player: &active_player::Root,
+        ability_ranks: &AbilityRanks,
+        _target: &dmg::Target,
+    ) -> Damage {
+        if step.action != Action::Ability(Ability::Attack) || ability_ranks.q_rank < 1 {
+            return Damage::default();
+        }
+        let charged = used_abilities(
+            after_last(previous, |a| a == Ability::Attack),
+            ability_ranks,
+        )
+        .any(|ability| ability == Ability::Q);
+        if !charged {
+            return Damage::default();
+        }
+        let rank = (ability_ranks.q_rank as usize).min(Q_DISCHARGE.len());
+        Damage::new(
+            Q_DISCHARGE[rank - 1]
+                + Q_DISCHARGE_AP_RATIO * active_player.champion_stats.ability_power,
+            DamageType::Magic,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::champions::fixtures::{assert_close, burst, champion, player, ranks};
+
+    #[test]
+    fn viktor_siphon_power_charges_the_next_auto_attack() {
+        let viktor = champion("Viktor");
+        let (player, ranks) = (player(5, 100.0), ranks(2, 1, 1, 0));
+        assert_close(burst(viktor.as_ref(), &player, &ranks, "QA", 0.0), 220.0);
+        assert_close(burst(viktor.as_ref(), &player, &ranks, "QAA", 0.0), 220.0);
+    }
+}
@@ -0,0 +1,50 @@
+use super::{definition::ChampionDefinition, Ability, Champion};
+use crate::{active_player, dmg::Damage, AbilityRanks};
+
+// Rite of the Arcane fires three shots at rank 1 and one more per rank after that
+const R_BASE_SHOTS: i64 = 2;
+
+#[derive(Debug)]
+pub struct Xerath {
+    definition: ChampionDefinition,
+}
+
+impl Xerath {
+    pub fn build(definition: ChampionDefinition) -> Xerath {
+        Xerath { definition }
+    }
+}
+
+impl Champion for Xerath {
+    fn definition(&self) -> &ChampionDefinition {
+        &self.definition
+    }
+
+    fn raw_damage(
+        &self,
+        ability: Ability,
+        active_player: &active_player::Root,
+        ability_ranks: &AbilityRanks,
+    ) -> Damage {
+        let damage = self
+            .definition
+            .raw_damage(ability, active_player, ability_ranks);
+        match ability {
+            Ability::R => damage * (R_BASE_SHOTS + ability_ranks.r_rank) as f64,
+            _ => damage,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::champions::fixtures::{assert_close, champion, player, ranks};
+
+    #[test]
+    fn xerath_rite_of_the_arcane_fires_more_shots_per_rank() {
+        let xerath = champion("Xerath");
+        let damage = xerath.raw_damage(Ability::R, &player(16, 100.0), &ranks(5, 5, 3, 3));
+        assert_close(damage.magic, 1725.0);
+    }
+}
@@ -0,0 +1,92 @@
+use super::{
+    after_last, definition::ChampionDefinition, is_spell, used_abilities, Ability, Champion,
+};
+use crate::{
+    active_player,
+    dmg::{self, Damage, DamageType},
+    rotation::{Action, Step},
+    AbilityRanks,
+};
+
+// Paddle Star deals bonus damage by champion level on top of its per rank damage, assuming
+// it doesn't travel far enough to deal increased damage
+const Q_LEVEL_DAMAGE: [f64; 18] = [
+    7.0, 8.0, 10.0, 12.0, 14.0, 16.0, 18.0, 20.0, 22.0, 24.0, 26.0, 29.0, 32.0, 35.0, 38.0, 41.0,
+    44.0, 46.0,
+];
+
+#[derive(Debug)]
+pub struct Zoe {
+    definition: ChampionDefinition,
+}
+
+impl Zoe {
+    pub fn build(definition: ChampionDefinition) -> Zoe {
+        Zoe { definition }
+    }
+}
+
+impl Champion for Zoe {
+    fn definition(&self) -> &ChampionDefinition {
+        &self.definition
+    }
+
+    fn passive_reads_previous(&self) -> bool {
+        true
+    }
+
+    fn raw_damage(
+        &self,
+        ability: Ability,
+        active_player: &active_player::Root,
+        ability_ranks: &AbilityRanks,
+    ) -> Damage {
+        let damage = self
+            .definition
+            .raw_damage(ability, active_player, ability_ranks);
+        match ability {
+            Ability::Q if ability_ranks.q_rank > 0 => {
+                let level = (active_player.level as usize).clamp(1, Q_LEVEL_DAMAGE.len());
+                damage + Damage::new(Q_LEVEL_DAMAGE[level - 1], DamageType::Magic)
+            }
+            _ => damage,
+        }
+    }
+
+    // More Sparkles!: the first auto attack after an ability deals bonus magic damage
+    fn passive_bonus(
+        &self,
+        previous: &[Step],
+        step: &Step,
+        active_player: &active_player::Root,
+        ability_ranks: &AbilityRanks,
+        _target: &dmg::Target,
+    ) -> Damage {
+        if step.action != Action::Ability(Ability::Attack) {
+            return Damage::default();
+        }
+        let sparkling = used_abilities(
+            after_last(previous, |a| a == Ability::Attack),
+            ability_ranks,
+        )
+        .any(is_spell);
+        if sparkling {
+            self.raw_damage(Ability::Passive, active_player, ability_ranks)
+        } else {
+            Damage::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::champions::fixtures::{assert_close, burst, champion, player, ranks};
+
+    #[test]
+    fn zoe_more_sparkles_empowers_the_auto_attack_after_an_ability() {
+        let zoe = champion("Zoe");
+        let (player, ranks) = (player(5, 100.0), ranks(1, 1, 1, 0));
+        assert_close(burst(zoe.as_ref(), &player, &ranks, "QA", 0.0), 176.0);
+        assert_close(burst(zoe.as_ref(), &player, &ranks, "QAA", 0.0), 176.0);
+    }
+}
@@ -0,0 +1,355 @@
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use reqwest::Client;
+use serde_json::Value;
+
+use crate::network;
+
+// Data Dragon files the app reads, by file name without the extension
+const CHAMPIONS: &str = "champion";
+const ITEMS: &str = "item";
+
+// Value of DDRAGON_VERSION that resolves the patch from the running game
+pub const AUTO_VERSION: &str = "auto";
+
+// Downloads give up after this long so offline runs fall back to the bundled snapshot
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(10);
+
+// Champion and item data of a single patch, empty by default
+#[derive(Default)]
+pub struct DataDragon {
+    pub version: String,
+    pub champions: Value,
+    pub items: Value,
+}
+
+// The bundled snapshot a patch falls back to couldn't be read
+#[derive(Debug)]
+pub struct BundledError {
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for BundledError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid bundled Data Dragon file {}: {}",
+            self.path, self.message
+        )
+    }
+}
+
+impl std::error::Error for BundledError {}
+
+// Data Dragon files cached on disk in a directory per patch version
+pub struct Cache {
+    dir: PathBuf,
+    // Snapshot shipped with the app, used when a patch isn't cached and can't be downloaded
+    bundled_dir: PathBuf,
+    url: String,
+    // Whether missing patches may be downloaded into the cache
+    online: bool,
+}
+
+impl Cache {
+    pub fn new(dir: &str, bundled_dir: &str, url: &str, online: bool) -> Self {
+        Cache {
+            dir: PathBuf::from(dir),
+            bundled_dir: PathBuf::from(bundled_dir),
+            url: url.trim_end_matches('/').to_string(),
+            online,
+        }
+    }
+
+    // Loads the patch from the cache, downloading it first when it isn't cached yet. Without
+    // a version the bundled snapshot is loaded.
+    pub async fn load(
+        &self,
+        client: &Client,
+        version: Option<&str>,
+    ) -> Result<DataDragon, BundledError> {
+        let version = match version {
+            Some(version) => version,
+            None => {
+                let bundled = self.bundled()?;
+                info!("Using the bundled Data Dragon {} snapshot", bundled.version);
+                return Ok(bundled);
+            }
+        };
+        let champions = self.file(client, version, CHAMPIONS).await;
+        let items = self.file(client, version, ITEMS).await;
+        match (champions, items) {
+            (Some(champions), Some(items)) => {
+                info!("Loaded Data Dragon {}", version);
+                Ok(DataDragon {
+                    version: version.to_string(),
+                    champions,
+                    items,
+                })
+            }
+            _ => {
+                let bundled = self.bundled()?;
+                warn!(
+                    "Data Dragon {} isn't cached and couldn't be downloaded, using the bundled {} snapshot",
+                    version, bundled.version
+                );
+                Ok(bundled)
+            }
+        }
+    }
+
+    // Data Dragon versions from newest to oldest. The manifest is downloaded again on every
+    // launch to pick up new patches, the cached copy is used when that isn't possible.
+    pub async fn versions(&self, client: &Client) -> Vec<String> {
+        let path = self.dir.join("versions.json");
+        let mut versions = None;
+        if self.online {
+            let url = format!("{}/api/versions.json", self.url);
+            versions = self.download(client, &url, &path).await;
+        }
+        let versions = versions.or_else(|| read_json(&path)).unwrap_or_default();
+        serde_json::from_value(versions).unwrap_or_default()
+    }
+
+    fn path(&self, version: &str, file: &str) -> PathBuf {
+        self.dir.join(version).join(format!("{}.json", file))
+    }
+
+    async fn file(&self, client: &Client, version: &str, file: &str) -> Option<Value> {
+        let path = self.path(version, file);
+        if let Some(value) = read_json(&path) {
+            debug!("Read {} from the Data Dragon cache", path.display());
+            return Some(value);
+        }
+        if !self.online {
+            return None;
+        }
+        let url = format!("{}/cdn/{}/data/en_US/{}.json", self.url, version, file);
+        self.download(client, &url, &path).await
+    }
+
+    // Downloads a JSON file and writes it to the cache
+    async fn download(&self, client: &Client, url: &str, path: &Path) -> Option<Value> {
+        let text = network::try_request(client, url, DOWNLOAD_TIMEOUT)
+            .await?
+            .text()
+            .await
+            .ok()?;
+        let value: Value = serde_json::from_str(&text).ok()?;
+        // A failed write only means the file is downloaded again on the next launch
+        let written =
+            fs::create_dir_all(path.parent().unwrap()).and_then(|_| fs::write(path, &text));
+        match written {
+            Ok(()) => info!("Cached {} in {}", url, path.display()),
+            Err(err) => warn!("Failed to cache {} in {}: {}", url, path.display(), err),
+        }
+        Some(value)
+    }
+
+    fn bundled(&self) -> Result<DataDragon, BundledError> {
+        let read = |file| {
+            let path = self.bundled_dir.join(format!("{}.json", file));
+            read_json(&path).ok_or_else(|| BundledError {
+                path: path.display().to_string(),
+                message: "missing or not valid JSON".to_string(),
+            })
+        };
+        let champions = read(CHAMPIONS)?;
+        let items = read(ITEMS)?;
+        let version = match champions["version"].as_str() {
+            Some(version) => version.to_string(),
+            None => {
+                return Err(BundledError {
+                    path: self.bundled_dir.join(CHAMPIONS).display().to_string(),
+                    message: "no version".to_string(),
+                })
+            }
+        };
+        Ok(DataDragon {
+            version,
+            champions,
+            items,
+        })
+    }
+}
+
+// Reads a JSON file, None if it doesn't exist or isn't valid JSON
+fn read_json(path: &Path) -> Option<Value> {
+    let text = fs::read_to_string(path).ok()?;
+    match serde_json::from_str(&text) {
+        Ok(value) => Some(value),
+        Err(err) => {
+            warn!("Ignoring invalid JSON in {}: {}", path.display(), err);
+            None
+        }
+    }
+}
+
+// Major and minor version of the patch a version is from, e.g. 12.13 for 12.13.1 or for the
+// game client version 12.13.453.3037
+pub fn patch(version: &str) -> Option<String> {
+    let mut parts = version.trim().split('.');
+    match (parts.next(), parts.next()) {
+        (Some(major), Some(minor))
+            if [major, minor]
+                .iter()
+                .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit())) =>
+        {
+            Some(format!("{}.{}", major, minor))
+        }
+        _ => None,
+    }
+}
+
+// Patch the game is on. The loaded Data Dragon version is only a guess at it, used while the
+// client hasn't reported its version, since it may be pinned or a fallback snapshot.
+pub fn game_patch(client_version: Option<&str>, loaded_version: &str) -> Option<String> {
+    client_version
+        .and_then(patch)
+        .or_else(|| patch(loaded_version))
+}
+
+// Data Dragon version to load. A configured version is used as is, otherwise the patch of the
+// game client, and the newest version in the manifest when the client doesn't report it.
+// None when nothing is known, leaving the bundled snapshot.
+pub fn resolve_version(
+    configured: &str,
+    client_version: Option<&str>,
+    versions: &[String],
+) -> Option<String> {
+    if configured != AUTO_VERSION {
+        return Some(configured.to_string());
+    }
+    match client_version.and_then(patch) {
+        Some(client_patch) => versions
+            .iter()
+            .find(|version| patch(version).as_deref() == Some(client_patch.as_str()))
+            .cloned()
+            .or(Some(format!("{}.1", client_patch))),
+        None => versions.first().cloned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Trimmed snapshot the tests load instead of the full bundled one
+    const FIXTURES: &str = "./resources/fixtures/ddragon/12.13.1";
+
+    // A cache in a fresh directory whose downloads fail, nothing listens on the discard port
+    fn offline_cache(name: &str, bundled_dir: &str) -> (Cache, PathBuf) {
+        let dir = std::env::temp_dir().join(format!("lolburst-{}-{}", name, std::process::id()));
+        let cache = Cache::new(
+            dir.to_str().unwrap(),
+            bundled_dir,
+            "http://127.0.0.1:9",
+            true,
+        );
+        (cache, dir)
+    }
+
+    #[tokio::test]
+    async fn load_falls_back_to_the_bundled_snapshot_when_the_download_fails() {
+        let (cache, dir) = offline_cache("ddragon-fallback", FIXTURES);
+        let data = cache.load(&Client::new(), Some("12.15.1")).await.unwrap();
+        assert_eq!(data.version, "12.13.1");
+        assert!(data.champions["data"]["Ashe"].is_object());
+        assert!(data.items["data"]["1001"].is_object());
+        // Nothing was downloaded, so nothing was cached
+        assert!(!dir.join("12.15.1").exists());
+    }
+
+    #[tokio::test]
+    async fn load_without_a_bundled_snapshot_is_an_error() {
+        let (cache, _) = offline_cache("ddragon-missing", "./resources/missing");
+        let err = cache
+            .load(&Client::new(), Some("12.15.1"))
+            .await
+            .err()
+            .unwrap();
+        assert!(err.path.ends_with("champion.json"), "{}", err);
+        assert!(cache.load(&Client::new(), None).await.is_err());
+    }
+
+    fn versions() -> Vec<String> {
+        ["12.14.1", "12.13.1", "12.12.1"]
+            .iter()
+            .map(|v| v.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn patch_of_ddragon_and_client_versions() {
+        assert_eq!(patch("12.13.1").as_deref(), Some("12.13"));
+        assert_eq!(patch("12.13.453.3037").as_deref(), Some("12.13"));
+        assert_eq!(patch(" 12.13.1\n").as_deref(), Some("12.13"));
+        assert_eq!(patch("lolpatch_7.20"), None);
+        assert_eq!(patch("12"), None);
+        assert_eq!(patch("12..1"), None);
+    }
+
+    #[test]
+    fn game_patch_prefers_the_client_version() {
+        assert_eq!(
+            game_patch(Some("12.15.460.1234"), "12.13.1").as_deref(),
+            Some("12.15")
+        );
+        assert_eq!(game_patch(None, "12.13.1").as_deref(), Some("12.13"));
+        assert_eq!(
+            game_patch(Some("unknown"), "12.13.1").as_deref(),
+            Some("12.13")
+        );
+        assert_eq!(game_patch(None, ""), None);
+    }
+
+    #[test]
+    fn configured_version_is_used_as_is() {
+        assert_eq!(
+            resolve_version("12.12.1", Some("12.13.453.3037"), &versions()).as_deref(),
+            Some("12.12.1")
+        );
+        assert_eq!(
+            resolve_version("11.1.1", None, &[]).as_deref(),
+            Some("11.1.1")
+        );
+    }
+
+    #[test]
+    fn auto_version_matches_the_client_patch() {
+        assert_eq!(
+            resolve_version(AUTO_VERSION, Some("12.13.453.3037"), &versions()).as_deref(),
+            Some("12.13.1")
+        );
+    }
+
+    #[test]
+    fn auto_version_guesses_the_first_release_of_an_unknown_client_patch() {
+        assert_eq!(
+            resolve_version(AUTO_VERSION, Some("12.15.460.1234"), &versions()).as_deref(),
+            Some("12.15.1")
+        );
+        assert_eq!(
+            resolve_version(AUTO_VERSION, Some("12.13.453.3037"), &[]).as_deref(),
+            Some("12.13.1")
+        );
+    }
+
+    #[test]
+    fn auto_version_without_a_client_patch_is_the_newest() {
+        assert_eq!(
+            resolve_version(AUTO_VERSION, None, &versions()).as_deref(),
+            Some("12.14.1")
+        );
+        assert_eq!(
+            resolve_version(AUTO_VERSION, Some("unknown"), &versions()).as_deref(),
+            Some("12.14.1")
+        );
+        assert_eq!(resolve_version(AUTO_VERSION, None, &[]), None);
+    }
+}
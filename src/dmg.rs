@@ -1,40 +1,555 @@
+use std::{
+    iter::Sum,
+    ops::{Add, AddAssign, Mul},
+};
+
+use serde::Deserialize;
+
 use crate::{
     active_player::{self, AbilityRanks},
-    champions::ActiveChampion,
+    champions::Champion,
+    loadout::Loadout,
+    rotation::Rotation,
+    summoners,
 };
 
+// Bisection steps used to find kill thresholds, enough to get within a health point
+const KILL_THRESHOLD_ITERATIONS: u32 = 16;
+
+// Resistance a source of damage is mitigated by, true damage ignores resistances and mixed
+// damage is split between physical and magic damage
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DamageType {
+    Physical,
+    Magic,
+    True,
+    Mixed,
+}
+
+// Damage before mitigation split by the resistance it's mitigated by
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Damage {
+    pub physical: f64,
+    pub magic: f64,
+    pub true_damage: f64,
+}
+
+impl Damage {
+    // Damage of a single type, mixed damage is split evenly
+    pub fn new(amount: f64, damage_type: DamageType) -> Self {
+        match damage_type {
+            DamageType::Physical => Damage {
+                physical: amount,
+                ..Default::default()
+            },
+            DamageType::Magic => Damage {
+                magic: amount,
+                ..Default::default()
+            },
+            DamageType::True => Damage {
+                true_damage: amount,
+                ..Default::default()
+            },
+            DamageType::Mixed => Damage::split(amount, 0.5),
+        }
+    }
+
+    // Mixed damage with the given share dealt as physical damage and the rest as magic damage
+    pub fn split(amount: f64, physical_share: f64) -> Self {
+        Damage {
+            physical: amount * physical_share,
+            magic: amount * (1.0 - physical_share),
+            true_damage: 0.0,
+        }
+    }
+
+    pub fn total(&self) -> f64 {
+        self.physical + self.magic + self.true_damage
+    }
+}
+
+impl Add for Damage {
+    type Output = Damage;
+
+    fn add(self, other: Damage) -> Damage {
+        Damage {
+            physical: self.physical + other.physical,
+            magic: self.magic + other.magic,
+            true_damage: self.true_damage + other.true_damage,
+        }
+    }
+}
+
+impl AddAssign for Damage {
+    fn add_assign(&mut self, other: Damage) {
+        *self = *self + other;
+    }
+}
+
+impl Mul<f64> for Damage {
+    type Output = Damage;
+
+    fn mul(self, multiplier: f64) -> Damage {
+        Damage {
+            physical: self.physical * multiplier,
+            magic: self.magic * multiplier,
+            true_damage: self.true_damage * multiplier,
+        }
+    }
+}
+
+impl Sum for Damage {
+    fn sum<I: Iterator<Item = Damage>>(iter: I) -> Damage {
+        iter.fold(Damage::default(), Add::add)
+    }
+}
+
+// Total resistances of a target along with the portion granted as bonus resistance
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Resistance {
     pub armor: f64,
+    pub bonus_armor: f64,
     pub magic_resist: f64,
+    pub bonus_magic_resist: f64,
 }
 
 impl Resistance {
-    pub fn new(armor: f64, magic_resist: f64) -> Self {
+    pub fn new(armor: f64, bonus_armor: f64, magic_resist: f64, bonus_magic_resist: f64) -> Self {
         Resistance {
             armor,
+            bonus_armor,
             magic_resist,
+            bonus_magic_resist,
+        }
+    }
+}
+
+// The enemy a burst is calculated against
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Target {
+    pub resistance: Resistance,
+    pub max_health: f64,
+    // Health the target has when the burst starts
+    pub current_health: f64,
+    // Shields the target can put up to absorb the burst
+    pub shield: f64,
+    // Shields that only absorb magic damage, like Hexdrinker's and Maw of Malmortius'
+    pub magic_shield: f64,
+    // Spell shields like Banshee's Veil's block the first ability to hit the target
+    pub spell_shield: bool,
+    // Order in which steps hitting several enemies reach the target, 0 for the main target
+    pub position: u32,
+}
+
+// Resistance reduction and penetration applied to a single resistance, as fractions
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Penetration {
+    // Reductions come from debuffs on the target the Live Client doesn't report, so they're
+    // left at zero until debuffs like Black Cleaver's are modeled
+    pub flat_reduction: f64,
+    pub percent_reduction: f64,
+    pub percent: f64,
+    pub bonus_percent: f64,
+    pub flat: f64,
+}
+
+// The active player's armor and magic penetration
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Penetrations {
+    pub armor: Penetration,
+    pub magic: Penetration,
+}
+
+impl Penetrations {
+    pub fn new(active_player: &active_player::Root) -> Self {
+        let stats = &active_player.champion_stats;
+        // The Live Client reports percent penetration as the fraction of resistance left over
+        Penetrations {
+            armor: Penetration {
+                percent: 1.0 - stats.armor_penetration_percent,
+                bonus_percent: 1.0 - stats.bonus_armor_penetration_percent,
+                flat: stats.armor_penetration_flat
+                    + lethality_to_flat(stats.physical_lethality, active_player.level),
+                ..Default::default()
+            },
+            magic: Penetration {
+                percent: 1.0 - stats.magic_penetration_percent,
+                bonus_percent: 1.0 - stats.bonus_magic_penetration_percent,
+                flat: stats.magic_penetration_flat + stats.magic_lethality,
+                ..Default::default()
+            },
         }
     }
 }
 
+// Lethality is converted into flat armor penetration based on the attacker's level
+fn lethality_to_flat(lethality: f64, level: i64) -> f64 {
+    lethality * (0.6 + 0.4 * level as f64 / 18.0)
+}
+
 pub fn burst_dmg(
-    active_champion: &ActiveChampion,
+    champion: &dyn Champion,
     active_player: &active_player::Root,
+    loadout: &Loadout,
     ability_ranks: &AbilityRanks,
-    resistance: Resistance,
+    rotation: &Rotation,
+    target: &Target,
 ) -> f64 {
-    match active_champion {
-        ActiveChampion::Orianna(orianna) => crate::orianna::Orianna::calculate_damage(
-            orianna,
+    // Damaging summoner spells are used in every burst they're available for
+    let rotation = summoners::with_summoner_spells(rotation, &loadout.summoner_spells);
+    champion.calculate_damage(active_player, loadout, &rotation, ability_ranks, target)
+}
+
+// Whether the burst takes the target's health down to zero through their shields
+pub fn kills(
+    champion: &dyn Champion,
+    active_player: &active_player::Root,
+    loadout: &Loadout,
+    ability_ranks: &AbilityRanks,
+    rotation: &Rotation,
+    target: &Target,
+) -> bool {
+    let rotation = summoners::with_summoner_spells(rotation, &loadout.summoner_spells);
+    champion
+        .damaged_target(active_player, loadout, &rotation, ability_ranks, target)
+        .current_health
+        <= 0.0
+}
+
+impl Target {
+    // Health and shields the target has, magic shields only hold against magic damage
+    pub fn effective_health(&self) -> f64 {
+        self.current_health + self.shield + self.magic_shield
+    }
+
+    pub fn missing_health(&self) -> f64 {
+        self.max_health - self.current_health
+    }
+
+    // The target after taking the mitigated magic and other damage. Magic shields absorb magic
+    // damage first, then shields absorb whatever is left before health does.
+    pub fn damaged(&self, magic: f64, other: f64) -> Target {
+        let magic_absorbed = magic.min(self.magic_shield);
+        let dealt = magic - magic_absorbed + other;
+        let absorbed = dealt.min(self.shield);
+        Target {
+            current_health: (self.current_health - (dealt - absorbed)).max(0.0),
+            shield: self.shield - absorbed,
+            magic_shield: self.magic_shield - magic_absorbed,
+            ..*self
+        }
+    }
+}
+
+// Highest health the target can start the burst with and still die to the rotation. The
+// burst only gets more lethal the less health the target has, so the threshold is bisected.
+pub fn kill_threshold(
+    champion: &dyn Champion,
+    active_player: &active_player::Root,
+    loadout: &Loadout,
+    ability_ranks: &AbilityRanks,
+    rotation: &Rotation,
+    target: &Target,
+) -> f64 {
+    let kills = |health: f64| {
+        let target = Target {
+            current_health: health,
+            ..*target
+        };
+        kills(
+            champion,
             active_player,
-            std::env::var("ROTATION").unwrap().as_str(),
+            loadout,
             ability_ranks,
-            resistance,
+            rotation,
+            &target,
+        )
+    };
+    if kills(target.max_health) {
+        return target.max_health;
+    }
+    let (mut low, mut high) = (0.0, target.max_health);
+    for _ in 0..KILL_THRESHOLD_ITERATIONS {
+        let health = (low + high) / 2.0;
+        if kills(health) {
+            low = health;
+        } else {
+            high = health;
+        }
+    }
+    low
+}
+
+// Total damage of the rotation against enemies clustered together, the first being the main
+// target. Steps without an explicit target count hit as many enemies as their ability can.
+pub fn teamfight_dmg(
+    champion: &dyn Champion,
+    active_player: &active_player::Root,
+    loadout: &Loadout,
+    ability_ranks: &AbilityRanks,
+    rotation: &Rotation,
+    targets: &[Target],
+) -> f64 {
+    let mut rotation = rotation.clone();
+    for step in rotation.steps.iter_mut() {
+        step.targets.get_or_insert(targets.len() as u32);
+    }
+    (0..)
+        .zip(targets)
+        .map(|(position, target)| {
+            let target = Target {
+                position,
+                ..*target
+            };
+            burst_dmg(
+                champion,
+                active_player,
+                loadout,
+                ability_ranks,
+                &rotation,
+                &target,
+            )
+        })
+        .sum()
+}
+
+// Mitigates each part of the damage by the resistance it's dealt against, the only place
+// resistances and penetration are applied
+pub fn mitigate(damage: Damage, resistance: &Resistance, penetrations: &Penetrations) -> f64 {
+    let physical = calculate_mitigation(
+        damage.physical,
+        effective_resistance(
+            resistance.armor,
+            resistance.bonus_armor,
+            &penetrations.armor,
         ),
-        _ => 0.0,
+    );
+    let magic = calculate_mitigation(
+        damage.magic,
+        effective_resistance(
+            resistance.magic_resist,
+            resistance.bonus_magic_resist,
+            &penetrations.magic,
+        ),
+    );
+    physical + magic + damage.true_damage
+}
+
+// Applies reduction and penetration in the in-game order: flat reduction, percent
+// reduction, percent penetration, bonus percent penetration, then flat penetration
+pub fn effective_resistance(resistance: f64, bonus: f64, penetration: &Penetration) -> f64 {
+    // Flat reduction is split between base and bonus resistance and can go below zero
+    let mut base = resistance - bonus;
+    let mut bonus = bonus;
+    if resistance > 0.0 {
+        base -= penetration.flat_reduction * base / resistance;
+        bonus -= penetration.flat_reduction * bonus / resistance;
+    } else {
+        base -= penetration.flat_reduction;
+    }
+
+    // Percent reduction and penetration only apply to positive resistance
+    if base + bonus <= 0.0 {
+        return base + bonus;
     }
+    let multiplier = (1.0 - penetration.percent_reduction) * (1.0 - penetration.percent);
+    base *= multiplier;
+    bonus *= multiplier * (1.0 - penetration.bonus_percent);
+
+    // Flat penetration can't reduce resistance below zero
+    (base + bonus - penetration.flat).max(0.0)
 }
 
 pub fn calculate_mitigation(rd: f64, resistance: f64) -> f64 {
-    rd / (1.0 + (resistance / 100.0))
+    if resistance >= 0.0 {
+        rd / (1.0 + (resistance / 100.0))
+    } else {
+        // Negative resistance amplifies damage instead
+        rd * (2.0 - 100.0 / (100.0 - resistance))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-6,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn reductions_and_penetration_apply_in_order() {
+        // 60 base and 40 bonus armor, reduced by 10 then 30%, penetrated by Lord Dominik's
+        // 35%, 45% bonus armor penetration and 10 flat penetration
+        let penetration = Penetration {
+            flat_reduction: 10.0,
+            percent_reduction: 0.3,
+            percent: 0.35,
+            bonus_percent: 0.45,
+            flat: 10.0,
+        };
+        // Base 60 -> 54 -> 37.8 -> 24.57, bonus 40 -> 36 -> 25.2 -> 16.38 -> 9.009
+        assert_close(
+            effective_resistance(100.0, 40.0, &penetration),
+            24.57 + 9.009 - 10.0,
+        );
+    }
+
+    #[test]
+    fn bonus_percent_penetration_only_applies_to_bonus_resistance() {
+        let penetration = Penetration {
+            bonus_percent: 0.4,
+            ..Default::default()
+        };
+        assert_close(effective_resistance(150.0, 50.0, &penetration), 130.0);
+        assert_close(effective_resistance(100.0, 0.0, &penetration), 100.0);
+    }
+
+    #[test]
+    fn flat_penetration_stops_at_zero() {
+        let penetration = Penetration {
+            flat: 30.0,
+            ..Default::default()
+        };
+        assert_close(effective_resistance(20.0, 0.0, &penetration), 0.0);
+        assert_close(effective_resistance(-10.0, 0.0, &penetration), -10.0);
+    }
+
+    #[test]
+    fn flat_reduction_goes_below_zero_and_skips_percent_penetration() {
+        let penetration = Penetration {
+            flat_reduction: 30.0,
+            percent: 0.35,
+            ..Default::default()
+        };
+        assert_close(effective_resistance(20.0, 10.0, &penetration), -10.0);
+        assert_close(effective_resistance(0.0, 0.0, &penetration), -30.0);
+    }
+
+    #[test]
+    fn lethality_scales_with_the_attackers_level() {
+        // Youmuu's Ghostblade's 18 lethality
+        assert_close(lethality_to_flat(18.0, 1), 11.2);
+        assert_close(lethality_to_flat(18.0, 9), 14.4);
+        assert_close(lethality_to_flat(18.0, 18), 18.0);
+
+        let mut active_player = active_player::Root {
+            level: 18,
+            ..Default::default()
+        };
+        active_player.champion_stats.physical_lethality = 18.0;
+        active_player.champion_stats.armor_penetration_flat = 5.0;
+        // The Live Client reports percent penetration as the resistance left over
+        active_player.champion_stats.armor_penetration_percent = 0.65;
+        active_player.champion_stats.bonus_armor_penetration_percent = 1.0;
+        let armor = Penetrations::new(&active_player).armor;
+        assert_close(armor.flat, 23.0);
+        assert_close(armor.percent, 0.35);
+        assert_close(armor.bonus_percent, 0.0);
+    }
+
+    fn shielded(shield: f64, magic_shield: f64) -> Target {
+        Target {
+            resistance: Resistance::new(0.0, 0.0, 0.0, 0.0),
+            max_health: 1000.0,
+            current_health: 1000.0,
+            shield,
+            magic_shield,
+            spell_shield: false,
+            position: 0,
+        }
+    }
+
+    #[test]
+    fn magic_shields_only_absorb_magic_damage() {
+        let target = shielded(0.0, 200.0).damaged(0.0, 300.0);
+        assert_close(target.current_health, 700.0);
+        assert_close(target.magic_shield, 200.0);
+
+        let target = shielded(0.0, 200.0).damaged(300.0, 0.0);
+        assert_close(target.current_health, 900.0);
+        assert_close(target.magic_shield, 0.0);
+    }
+
+    #[test]
+    fn shields_absorb_magic_damage_left_over_by_magic_shields() {
+        let target = shielded(100.0, 200.0).damaged(250.0, 100.0);
+        assert_close(target.magic_shield, 0.0);
+        assert_close(target.shield, 0.0);
+        assert_close(target.current_health, 950.0);
+        assert_close(shielded(0.0, 0.0).damaged(800.0, 800.0).current_health, 0.0);
+    }
+
+    #[test]
+    fn physical_damage_is_mitigated_by_armor() {
+        let resistance = Resistance::new(100.0, 0.0, 50.0, 0.0);
+        let penetrations = Penetrations::default();
+        assert_close(
+            mitigate(
+                Damage::new(200.0, DamageType::Physical),
+                &resistance,
+                &penetrations,
+            ),
+            100.0,
+        );
+        assert_close(
+            mitigate(
+                Damage::new(150.0, DamageType::Magic),
+                &resistance,
+                &penetrations,
+            ),
+            100.0,
+        );
+    }
+
+    #[test]
+    fn true_damage_ignores_resistances() {
+        let resistance = Resistance::new(100.0, 0.0, 50.0, 0.0);
+        assert_close(
+            mitigate(
+                Damage::new(100.0, DamageType::True),
+                &resistance,
+                &Penetrations::default(),
+            ),
+            100.0,
+        );
+    }
+
+    #[test]
+    fn mixed_damage_is_mitigated_by_both_resistances() {
+        let resistance = Resistance::new(100.0, 0.0, 50.0, 0.0);
+        let penetrations = Penetrations::default();
+        // Split evenly, 100 physical damage against 100 armor and 100 magic against 50 MR
+        assert_close(
+            mitigate(
+                Damage::new(200.0, DamageType::Mixed),
+                &resistance,
+                &penetrations,
+            ),
+            50.0 + 100.0 / 1.5,
+        );
+        assert_close(
+            mitigate(Damage::split(300.0, 1.0 / 3.0), &resistance, &penetrations),
+            50.0 + 200.0 / 1.5,
+        );
+    }
+
+    #[test]
+    fn resistance_mitigates_damage() {
+        assert_close(calculate_mitigation(100.0, 0.0), 100.0);
+        assert_close(calculate_mitigation(100.0, 100.0), 50.0);
+        assert_close(calculate_mitigation(100.0, 300.0), 25.0);
+    }
+
+    #[test]
+    fn negative_resistance_amplifies_damage() {
+        assert_close(calculate_mitigation(100.0, -25.0), 120.0);
+        assert_close(calculate_mitigation(100.0, -100.0), 150.0);
+    }
 }
@@ -0,0 +1,373 @@
+use std::collections::HashMap;
+
+use crate::{
+    active_player, all_players,
+    champions::definition::ChampionDefinition,
+    dmg::{self, Damage, DamageType},
+};
+
+type DamageFn = fn(&active_player::Root, &ChampionDefinition, &dmg::Target) -> Damage;
+
+// How an item's passive or active adds to a burst
+#[derive(Clone, Copy)]
+pub enum ItemEffect {
+    // Damage dealt when the item is used as an item active step
+    Active(DamageFn),
+    // Damage added to every auto attack
+    OnHit(DamageFn),
+    // Damage added to the first ability that hits
+    AbilityHit(DamageFn),
+    // Damage added to the first auto attack after an ability, only the strongest applies
+    Spellblade(DamageFn),
+    // Increase of all damage dealt to the target
+    Amplifier(f64),
+}
+
+impl std::fmt::Debug for ItemEffect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ItemEffect::Active(_) => write!(f, "Active"),
+            ItemEffect::OnHit(_) => write!(f, "OnHit"),
+            ItemEffect::AbilityHit(_) => write!(f, "AbilityHit"),
+            ItemEffect::Spellblade(_) => write!(f, "Spellblade"),
+            ItemEffect::Amplifier(amplifier) => write!(f, "Amplifier({})", amplifier),
+        }
+    }
+}
+
+// Ranged champions get weaker versions of some on-hit effects
+fn ranged(active_player: &active_player::Root) -> bool {
+    active_player.champion_stats.attack_range > 300.0
+}
+
+fn base_attack_damage(active_player: &active_player::Root, definition: &ChampionDefinition) -> f64 {
+    active_player.champion_stats.attack_damage - definition.bonus_attack_damage(active_player)
+}
+
+// Damaging item effects keyed by item id
+fn registry() -> HashMap<i64, ItemEffect> {
+    let mut registry: HashMap<i64, ItemEffect> = HashMap::new();
+    // Recurve Bow
+    registry.insert(
+        1043,
+        ItemEffect::OnHit(|_, _, _| Damage::new(15.0, DamageType::Physical)),
+    );
+    // Wit's End
+    registry.insert(
+        3091,
+        ItemEffect::OnHit(|ap, _, _| {
+            Damage::new(
+                15.0 + 65.0 * (ap.level as f64 - 1.0) / 17.0,
+                DamageType::Magic,
+            )
+        }),
+    );
+    // Nashor's Tooth
+    registry.insert(
+        3115,
+        ItemEffect::OnHit(|ap, _, _| {
+            Damage::new(
+                15.0 + 0.2 * ap.champion_stats.ability_power,
+                DamageType::Magic,
+            )
+        }),
+    );
+    // Blade of The Ruined King
+    registry.insert(
+        3153,
+        ItemEffect::OnHit(|ap, _, target| {
+            let share = if ranged(ap) { 0.09 } else { 0.12 };
+            Damage::new(share * target.current_health, DamageType::Physical)
+        }),
+    );
+    // Sheen
+    registry.insert(
+        3057,
+        ItemEffect::Spellblade(|ap, d, _| {
+            Damage::new(base_attack_damage(ap, d), DamageType::Physical)
+        }),
+    );
+    // Trinity Force
+    registry.insert(
+        3078,
+        ItemEffect::Spellblade(|ap, d, _| {
+            Damage::new(2.0 * base_attack_damage(ap, d), DamageType::Physical)
+        }),
+    );
+    // Lich Bane
+    registry.insert(
+        3100,
+        ItemEffect::Spellblade(|ap, d, _| {
+            Damage::new(
+                0.75 * base_attack_damage(ap, d) + 0.5 * ap.champion_stats.ability_power,
+                DamageType::Magic,
+            )
+        }),
+    );
+    // Hextech Rocketbelt
+    registry.insert(
+        3152,
+        ItemEffect::Active(|ap, _, _| {
+            Damage::new(
+                125.0 + 0.15 * ap.champion_stats.ability_power,
+                DamageType::Magic,
+            )
+        }),
+    );
+    // Horizon Focus, assuming the burst starts from long range to trigger Hypershot
+    registry.insert(4628, ItemEffect::Amplifier(1.1));
+    // Stridebreaker
+    registry.insert(
+        6631,
+        ItemEffect::Active(|ap, _, _| {
+            Damage::new(ap.champion_stats.attack_damage, DamageType::Physical)
+        }),
+    );
+    // Liandry's Anguish, the full burn
+    registry.insert(
+        6653,
+        ItemEffect::AbilityHit(|ap, _, target| {
+            Damage::new(
+                60.0 + 0.06 * ap.champion_stats.ability_power + 0.04 * target.max_health,
+                DamageType::Magic,
+            )
+        }),
+    );
+    // Luden's Tempest
+    registry.insert(
+        6655,
+        ItemEffect::AbilityHit(|ap, _, _| {
+            Damage::new(
+                100.0 + 0.1 * ap.champion_stats.ability_power,
+                DamageType::Magic,
+            )
+        }),
+    );
+    // Everfrost
+    registry.insert(
+        6656,
+        ItemEffect::Active(|ap, _, _| {
+            Damage::new(
+                100.0 + 0.3 * ap.champion_stats.ability_power,
+                DamageType::Magic,
+            )
+        }),
+    );
+    registry
+}
+
+// Effects of the items in the inventory along with the slot the item is in
+pub fn item_effects(items: &[all_players::Item]) -> Vec<(i64, ItemEffect)> {
+    let registry = registry();
+    items
+        .iter()
+        .filter_map(|item| registry.get(&item.item_id).map(|e| (item.slot, *e)))
+        .collect()
+}
+
+// Item passives procced so far, which decide what later hits proc
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ProcState {
+    ability_hit: bool,
+    spellblade_ready: bool,
+}
+
+// Tracks which item passives have procced over the steps of a single rotation
+#[derive(Clone)]
+pub struct Procs<'a> {
+    effects: &'a [(i64, ItemEffect)],
+    active_player: &'a active_player::Root,
+    definition: &'a ChampionDefinition,
+    state: ProcState,
+}
+
+impl<'a> Procs<'a> {
+    pub fn new(
+        effects: &'a [(i64, ItemEffect)],
+        active_player: &'a active_player::Root,
+        definition: &'a ChampionDefinition,
+    ) -> Self {
+        Procs {
+            effects,
+            active_player,
+            definition,
+            state: ProcState::default(),
+        }
+    }
+
+    pub fn state(&self) -> ProcState {
+        self.state
+    }
+
+    // Damage of the active of the item in the slot, nothing if it has no damaging active
+    pub fn active(&self, slot: i64, target: &dmg::Target) -> Damage {
+        self.effects
+            .iter()
+            .filter(|(s, _)| *s == slot)
+            .map(|(_, effect)| match effect {
+                ItemEffect::Active(damage) => damage(self.active_player, self.definition, target),
+                _ => Damage::default(),
+            })
+            .sum()
+    }
+
+    // Bonus damage added to an ability hit
+    pub fn ability_hit(&mut self, target: &dmg::Target) -> Damage {
+        self.state.spellblade_ready = true;
+        if self.state.ability_hit {
+            return Damage::default();
+        }
+        self.state.ability_hit = true;
+        self.effects
+            .iter()
+            .map(|(_, effect)| match effect {
+                ItemEffect::AbilityHit(damage) => {
+                    damage(self.active_player, self.definition, target)
+                }
+                _ => Damage::default(),
+            })
+            .sum()
+    }
+
+    // Bonus damage added to an auto attack hit
+    pub fn attack_hit(&mut self, target: &dmg::Target) -> Damage {
+        if !self.state.spellblade_ready {
+            return Damage::default();
+        }
+        self.state.spellblade_ready = false;
+        self.effects
+            .iter()
+            .filter_map(|(_, effect)| match effect {
+                ItemEffect::Spellblade(damage) => {
+                    Some(damage(self.active_player, self.definition, target))
+                }
+                _ => None,
+            })
+            .max_by(|a, b| a.total().total_cmp(&b.total()))
+            .unwrap_or_default()
+    }
+
+    // Multiplier applied to all damage dealt to the target
+    pub fn amplifier(&self) -> f64 {
+        self.effects
+            .iter()
+            .map(|(_, effect)| match effect {
+                ItemEffect::Amplifier(amplifier) => *amplifier,
+                _ => 1.0,
+            })
+            .product()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::champions::definition;
+
+    const SHEEN: i64 = 3057;
+    const LICH_BANE: i64 = 3100;
+    const BLADE_OF_THE_RUINED_KING: i64 = 3153;
+    const HEXTECH_ROCKETBELT: i64 = 3152;
+    const LUDENS_TEMPEST: i64 = 6655;
+    const EVERFROST: i64 = 6656;
+
+    fn lux() -> ChampionDefinition {
+        definition::load_definitions("./resources/champions").unwrap()["Lux"].clone()
+    }
+
+    // A level 1 Lux with ability power and no bonus attack damage
+    fn player(attack_range: f64) -> active_player::Root {
+        let mut player = active_player::Root {
+            level: 1,
+            ..Default::default()
+        };
+        player.champion_stats.ability_power = 100.0;
+        player.champion_stats.attack_damage = lux().base_stats.attack_damage;
+        player.champion_stats.attack_range = attack_range;
+        player
+    }
+
+    fn target(current_health: f64) -> dmg::Target {
+        dmg::Target {
+            resistance: dmg::Resistance::new(0.0, 0.0, 0.0, 0.0),
+            max_health: 2000.0,
+            current_health,
+            shield: 0.0,
+            magic_shield: 0.0,
+            spell_shield: false,
+            position: 0,
+        }
+    }
+
+    // Effects of the items, each in the slot of its position
+    fn effects(item_ids: &[i64]) -> Vec<(i64, ItemEffect)> {
+        let items: Vec<all_players::Item> = (0..)
+            .zip(item_ids)
+            .map(|(slot, item_id)| all_players::Item {
+                item_id: *item_id,
+                slot,
+                count: 1,
+                ..Default::default()
+            })
+            .collect();
+        item_effects(&items)
+    }
+
+    #[test]
+    fn spellblade_is_used_by_the_first_attack_after_an_ability() {
+        let (definition, player, target) = (lux(), player(550.0), target(2000.0));
+        let effects = effects(&[SHEEN, LICH_BANE]);
+        let mut procs = Procs::new(&effects, &player, &definition);
+        // Only Lich Bane applies, being stronger than Sheen
+        let lich_bane = 0.75 * definition.base_stats.attack_damage + 50.0;
+        assert_eq!(procs.attack_hit(&target).total(), 0.0);
+        procs.ability_hit(&target);
+        assert_eq!(procs.attack_hit(&target).total(), lich_bane);
+        assert_eq!(procs.attack_hit(&target).total(), 0.0);
+        procs.ability_hit(&target);
+        procs.ability_hit(&target);
+        assert_eq!(procs.attack_hit(&target).total(), lich_bane);
+    }
+
+    #[test]
+    fn ability_hit_effects_only_apply_to_the_first_ability() {
+        let (definition, player, target) = (lux(), player(550.0), target(2000.0));
+        let effects = effects(&[LUDENS_TEMPEST]);
+        let mut procs = Procs::new(&effects, &player, &definition);
+        assert_eq!(procs.attack_hit(&target).total(), 0.0);
+        assert_eq!(procs.ability_hit(&target).total(), 110.0);
+        assert_eq!(procs.ability_hit(&target).total(), 0.0);
+    }
+
+    #[test]
+    fn ruined_king_scales_with_current_health_and_range() {
+        let definition = lux();
+        let on_hit = match registry()[&BLADE_OF_THE_RUINED_KING] {
+            ItemEffect::OnHit(damage) => damage,
+            effect => panic!("Unexpected effect {:?}", effect),
+        };
+        assert_eq!(
+            on_hit(&player(125.0), &definition, &target(1000.0)).total(),
+            120.0
+        );
+        assert_eq!(
+            on_hit(&player(550.0), &definition, &target(1000.0)).total(),
+            90.0
+        );
+        assert_eq!(
+            on_hit(&player(550.0), &definition, &target(500.0)).total(),
+            45.0
+        );
+    }
+
+    #[test]
+    fn actives_are_matched_by_slot() {
+        let (definition, player, target) = (lux(), player(550.0), target(2000.0));
+        let effects = effects(&[SHEEN, EVERFROST, HEXTECH_ROCKETBELT]);
+        let procs = Procs::new(&effects, &player, &definition);
+        assert_eq!(procs.active(0, &target).total(), 0.0);
+        assert_eq!(procs.active(1, &target).total(), 130.0);
+        assert_eq!(procs.active(2, &target).total(), 140.0);
+        assert_eq!(procs.active(3, &target).total(), 0.0);
+    }
+}
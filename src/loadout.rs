@@ -0,0 +1,59 @@
+use std::str::FromStr;
+
+use crate::{
+    active_player, all_players,
+    item_effects::{self, ItemEffect},
+    runes::Runes,
+    summoners::SummonerSpell,
+    utils::teams,
+};
+
+// How critical strikes are counted in auto attack damage
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CritMode {
+    // Average damage weighted by crit chance
+    Expected,
+    // Every attack crits when the active player has any crit chance
+    Max,
+}
+
+impl FromStr for CritMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "expected" => Ok(CritMode::Expected),
+            "max" => Ok(CritMode::Max),
+            _ => Err(format!(
+                "Unknown crit mode {}, expected \"expected\" or \"max\"",
+                s
+            )),
+        }
+    }
+}
+
+// The active player's items and settings used for damage that doesn't come from abilities
+#[derive(Debug, Clone)]
+pub struct Loadout {
+    // Damaging item effects along with the inventory slot of their item
+    pub item_effects: Vec<(i64, ItemEffect)>,
+    pub summoner_spells: [SummonerSpell; 2],
+    pub runes: Runes,
+    pub crit_mode: CritMode,
+}
+
+impl Loadout {
+    pub fn new(
+        active_player: &active_player::Root,
+        players: &all_players::Root,
+        crit_mode: CritMode,
+    ) -> Self {
+        let player = teams::active_all_player(active_player, players);
+        Loadout {
+            item_effects: item_effects::item_effects(&player.items),
+            summoner_spells: SummonerSpell::from_player(player),
+            runes: Runes::new(active_player),
+            crit_mode,
+        }
+    }
+}
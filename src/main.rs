@@ -2,7 +2,6 @@ extern crate pretty_env_logger;
 #[macro_use]
 extern crate log;
 
-use crate::champions::orianna;
 use active_player::AbilityRanks;
 use crossterm::{
     event::{DisableMouseCapture, EnableMouseCapture},
@@ -15,10 +14,19 @@ use tui::{backend::CrosstermBackend, Terminal};
 mod active_player;
 mod all_players;
 mod app;
+mod attack;
 mod champions;
+mod ddragon;
 mod dmg;
 mod game_data;
+mod item_effects;
+mod loadout;
 mod network;
+mod optimizer;
+mod rotation;
+mod runes;
+mod stacks;
+mod summoners;
 mod ui;
 mod utils;
 
@@ -39,12 +47,23 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Set default level for unknown targets to Trace
     tui_logger::set_default_level(log::LevelFilter::Trace);
 
+    // Parse the rotations and champion definitions before taking over the terminal so errors
+    // are readable
+    let rotations =
+        rotation::parse_named(&std::env::var("ROTATIONS").expect("ROTATIONS is not set"))?;
+    for r in rotations.iter() {
+        info!("Using rotation {}: {}", r.name, r.rotation);
+    }
+    let champion_definitions = champions::definition::load_definitions(
+        &std::env::var("CHAMPION_DEFINITIONS").expect("CHAMPION_DEFINITIONS is not set"),
+    )?;
+
     // Setup terminal
     let mut terminal = setup_terminal()?;
 
     // Initialize app
     // Create app
-    let app = app::App::new();
+    let app = app::App::new(rotations, champion_definitions);
     // Run app
     let res = app::run_app(&mut terminal, app).await;
 
@@ -69,8 +88,7 @@ fn setup_terminal() -> Result<Terminal<CrosstermBackend<io::Stdout>>, std::io::E
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
-    let terminal = Terminal::new(backend);
-    terminal
+    Terminal::new(backend)
 }
 
 fn restore_terminal(
@@ -24,3 +24,21 @@ pub async fn request(client: &Client, url: &str) -> Response {
         }
     }
 }
+
+// Sends a single Get request, giving up after the timeout instead of retrying
+pub async fn try_request(client: &Client, url: &str, timeout: Duration) -> Option<Response> {
+    info!("Sending Get request to {}", url);
+    match client
+        .get(url)
+        .timeout(timeout)
+        .send()
+        .await
+        .and_then(|res| res.error_for_status())
+    {
+        Ok(res) => Some(res),
+        Err(err) => {
+            warn!("Didn't receive a response from {}: {}", url, err);
+            None
+        }
+    }
+}
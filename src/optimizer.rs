@@ -0,0 +1,431 @@
+use std::collections::HashMap;
+
+use crate::{
+    active_player::{self, AbilityRanks},
+    champions::{Ability, Burst, BurstState, Champion},
+    dmg,
+    loadout::Loadout,
+    rotation::{Action, Rotation, Step},
+    runes, summoners,
+};
+
+// Abilities the optimizer can use, auto attacks fill in the remaining time
+const ABILITIES: [Ability; 4] = [Ability::Q, Ability::W, Ability::E, Ability::R];
+
+// Resolution in seconds used to recognise equivalent points in the search
+const TIME_STEP: f64 = 0.05;
+
+// Number of sequences evaluated before settling for the best one found so far, abilities
+// are tried before auto attacks so strong sequences are found early
+const MAX_VISITS: usize = 10_000;
+
+// Highest damage rotation found for a target
+pub struct BestRotation {
+    pub rotation: Rotation,
+    pub damage: f64,
+    // Whether every sequence was searched, otherwise the search stopped at MAX_VISITS and
+    // a stronger rotation may exist
+    pub exhaustive: bool,
+}
+
+// Searches every legal sequence of abilities and auto attacks that finishes within the
+// window and returns the one dealing the most damage to the target. Abilities are recast
+// once their cooldown is over, abilities without a known cooldown are only used once, and
+// abilities are only cast while there's enough resource to pay for them. Searches cut short
+// at MAX_VISITS return the best rotation found so far.
+pub fn best_rotation(
+    champion: &dyn Champion,
+    active_player: &active_player::Root,
+    loadout: &Loadout,
+    ability_ranks: &AbilityRanks,
+    target: &dmg::Target,
+    window: f64,
+) -> BestRotation {
+    let conqueror = runes::conqueror_player(&loadout.runes, champion.definition(), active_player);
+    let burst = Burst::new(
+        champion.definition(),
+        active_player,
+        loadout,
+        ability_ranks,
+        target,
+        conqueror.as_ref(),
+    );
+    let mut search = Search {
+        champion,
+        active_player,
+        ability_ranks,
+        window,
+        visited: HashMap::new(),
+        visits: 0,
+        truncated: false,
+        best: BestRotation {
+            rotation: Rotation { steps: Vec::new() },
+            damage: 0.0,
+            exhaustive: true,
+        },
+    };
+    search.visit(&mut Vec::new(), &burst, 0.0, [0.0; 4], 0.0);
+
+    // Damaging summoner spells are instant, so they're added on top of the best sequence
+    let rotation = summoners::with_summoner_spells(&search.best.rotation, &loadout.summoner_spells);
+    let damage =
+        champion.calculate_damage(active_player, loadout, &rotation, ability_ranks, target);
+    BestRotation {
+        rotation,
+        damage,
+        exhaustive: !search.truncated,
+    }
+}
+
+// A point in the search, sequences reaching the same point can only deal the same damage
+// from there on. Champions whose passive reads the steps used before only reach the same
+// point with the same steps.
+#[derive(PartialEq, Eq, Hash)]
+struct Point {
+    ticks: u32,
+    cooldowns: [u32; 4],
+    spent: u32,
+    last: Option<Action>,
+    burst: BurstState,
+    previous: Vec<Action>,
+}
+
+struct Search<'a> {
+    champion: &'a dyn Champion,
+    active_player: &'a active_player::Root,
+    ability_ranks: &'a AbilityRanks,
+    window: f64,
+    // Best damage seen at each point of the search
+    visited: HashMap<Point, f64>,
+    visits: usize,
+    // Whether sequences were left unsearched after MAX_VISITS
+    truncated: bool,
+    best: BestRotation,
+}
+
+impl<'a> Search<'a> {
+    // burst holds the damage of the steps so far, so each sequence only deals its last step.
+    // ready_at holds the time each of ABILITIES comes off cooldown, infinite once used
+    // when it has no cooldown. spent is the resource used by the steps so far.
+    fn visit(
+        &mut self,
+        steps: &mut Vec<Step>,
+        burst: &Burst,
+        elapsed: f64,
+        ready_at: [f64; 4],
+        spent: f64,
+    ) {
+        if self.visits == MAX_VISITS {
+            self.truncated = true;
+            return;
+        }
+        self.visits += 1;
+
+        let damage = burst.dealt;
+        if damage > self.best.damage {
+            self.best = BestRotation {
+                rotation: Rotation {
+                    steps: steps.clone(),
+                },
+                damage,
+                exhaustive: true,
+            };
+        }
+
+        // Stop if another sequence already reached this point with at least as much damage
+        let key = Point {
+            ticks: to_ticks(elapsed),
+            cooldowns: ready_at.map(|t| to_ticks((t - elapsed).max(0.0))),
+            spent: spent.round() as u32,
+            last: steps.last().map(|s| s.action),
+            burst: burst.state(),
+            previous: if self.champion.passive_reads_previous() {
+                steps.iter().map(|s| s.action).collect()
+            } else {
+                Vec::new()
+            },
+        };
+        match self.visited.get(&key) {
+            Some(d) if *d >= damage => return,
+            _ => {
+                self.visited.insert(key, damage);
+            }
+        }
+
+        let mut ability_ready = false;
+        for (i, ability) in ABILITIES.iter().copied().enumerate() {
+            if !ability.is_learned(self.ability_ranks) {
+                continue;
+            }
+            // Abilities can't be cast without enough resource, which regenerates over time
+            let cost = self
+                .champion
+                .cost(ability, self.active_player, self.ability_ranks);
+            let stats = &self.active_player.champion_stats;
+            let affordable = |time| {
+                cost <= 0.0
+                    || spent + cost <= stats.resource_value + stats.resource_regen_rate * time
+            };
+            ability_ready |= ready_at[i] <= elapsed && affordable(elapsed);
+            let start = elapsed.max(ready_at[i]);
+            let end = start + self.champion.cast_time(ability, self.active_player);
+            if end > self.window || !affordable(start) {
+                continue;
+            }
+            let cooldown = self
+                .champion
+                .cooldown(ability, self.active_player, self.ability_ranks);
+            let mut next_ready_at = ready_at;
+            next_ready_at[i] = match cooldown {
+                Some(cooldown) => start + cooldown,
+                None => f64::INFINITY,
+            };
+            self.push(steps, burst, ability, end, next_ready_at, spent + cost);
+        }
+
+        // Auto attacks are weaved in once between abilities or fill time spent waiting for
+        // cooldowns or resource, and only when they deal damage
+        let after_attack = matches!(
+            steps.last(),
+            Some(Step {
+                action: Action::Ability(Ability::Attack),
+                ..
+            })
+        );
+        if !(ability_ready && after_attack)
+            && self
+                .champion
+                .raw_damage(Ability::Attack, self.active_player, self.ability_ranks)
+                .total()
+                > 0.0
+        {
+            let end = elapsed + self.champion.cast_time(Ability::Attack, self.active_player);
+            if end <= self.window {
+                self.push(steps, burst, Ability::Attack, end, ready_at, spent);
+            }
+        }
+    }
+
+    fn push(
+        &mut self,
+        steps: &mut Vec<Step>,
+        burst: &Burst,
+        ability: Ability,
+        end: f64,
+        ready_at: [f64; 4],
+        spent: f64,
+    ) {
+        let step = Step {
+            action: Action::Ability(ability),
+            targets: None,
+            optional: false,
+        };
+        let mut burst = burst.clone();
+        burst.step(self.champion, steps, &step);
+        steps.push(step);
+        self.visit(steps, &burst, end, ready_at, spent);
+        steps.pop();
+    }
+}
+
+fn to_ticks(seconds: f64) -> u32 {
+    if seconds.is_finite() {
+        (seconds / TIME_STEP).round() as u32
+    } else {
+        u32::MAX
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        champions::{self, definition},
+        loadout::CritMode,
+        runes::Runes,
+        summoners::SummonerSpell,
+    };
+
+    fn lux() -> Box<dyn Champion> {
+        champions::match_champion(
+            "Lux",
+            &definition::load_definitions("./resources/champions").unwrap(),
+        )
+        .unwrap()
+    }
+
+    // A level 6 Lux with plenty of mana
+    fn player() -> active_player::Root {
+        let mut player = active_player::Root {
+            level: 6,
+            ..Default::default()
+        };
+        let stats = &mut player.champion_stats;
+        stats.ability_power = 100.0;
+        stats.attack_damage = 70.0;
+        stats.attack_speed = 0.625;
+        stats.resource_type = "MANA".to_string();
+        stats.resource_value = 1000.0;
+        player
+    }
+
+    fn loadout() -> Loadout {
+        Loadout {
+            item_effects: Vec::new(),
+            summoner_spells: [SummonerSpell::Other; 2],
+            runes: Runes::default(),
+            crit_mode: CritMode::Expected,
+        }
+    }
+
+    fn target() -> dmg::Target {
+        dmg::Target {
+            resistance: dmg::Resistance::new(30.0, 0.0, 30.0, 0.0),
+            max_health: 2000.0,
+            current_health: 2000.0,
+            shield: 0.0,
+            magic_shield: 0.0,
+            spell_shield: false,
+            position: 0,
+        }
+    }
+
+    fn best(window: f64) -> BestRotation {
+        best_with(&player(), &AbilityRanks::new(1, 0, 1, 1), window)
+    }
+
+    fn best_with(player: &active_player::Root, ranks: &AbilityRanks, window: f64) -> BestRotation {
+        best_rotation(lux().as_ref(), player, &loadout(), ranks, &target(), window)
+    }
+
+    // Number of times the best rotation casts the ability
+    fn casts(best: &BestRotation, ability: Ability) -> usize {
+        best.rotation
+            .steps
+            .iter()
+            .filter(|step| step.action == Action::Ability(ability))
+            .count()
+    }
+
+    fn burst(rotation: &str) -> f64 {
+        lux().calculate_damage(
+            &player(),
+            &loadout(),
+            &rotation.parse().unwrap(),
+            &AbilityRanks::new(1, 0, 1, 1),
+            &target(),
+        )
+    }
+
+    #[test]
+    fn best_damage_is_the_damage_of_the_best_rotation() {
+        for window in [1.0, 3.0, 10.0] {
+            let best = best(window);
+            assert!((best.damage - burst(&best.rotation.to_string())).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn best_rotation_beats_rotations_that_fit_the_window() {
+        // Q, E and R take 1.5s to cast, an auto attack 1.6s
+        let best = best(3.5);
+        assert!(best.exhaustive);
+        for rotation in ["QER", "REQ", "EQRA", "QEA"] {
+            assert!(
+                best.damage >= burst(rotation),
+                "{} beats {}",
+                rotation,
+                best.rotation
+            );
+        }
+    }
+
+    // Every sequence of single casts and auto attacks that fits the window, starting with the
+    // empty one
+    fn sequences(window: f64) -> Vec<Vec<Ability>> {
+        let (lux, player) = (lux(), player());
+        let mut sequences: Vec<Vec<Ability>> = vec![Vec::new()];
+        let mut i = 0;
+        while i < sequences.len() {
+            let sequence = sequences[i].clone();
+            let elapsed: f64 = sequence.iter().map(|a| lux.cast_time(*a, &player)).sum();
+            for ability in [Ability::Q, Ability::E, Ability::R, Ability::Attack] {
+                let fits = elapsed + lux.cast_time(ability, &player) <= window;
+                if fits && (ability == Ability::Attack || !sequence.contains(&ability)) {
+                    let mut next = sequence.clone();
+                    next.push(ability);
+                    sequences.push(next);
+                }
+            }
+            i += 1;
+        }
+        sequences
+    }
+
+    #[test]
+    fn sequences_reaching_the_same_point_in_other_states_are_searched() {
+        // Illumination makes the order of abilities and auto attacks matter, so sequences
+        // with the same casts, cooldowns and mana spent still deal different damage
+        let best = best(5.0);
+        assert!(best.exhaustive);
+        for sequence in sequences(5.0).into_iter().skip(1) {
+            let rotation = Rotation {
+                steps: sequence
+                    .iter()
+                    .map(|ability| Step {
+                        action: Action::Ability(*ability),
+                        targets: None,
+                        optional: false,
+                    })
+                    .collect(),
+            };
+            assert!(
+                best.damage >= burst(&rotation.to_string()) - 1e-6,
+                "{} beats {}",
+                rotation,
+                best.rotation
+            );
+        }
+    }
+
+    #[test]
+    fn nothing_fits_a_window_shorter_than_any_cast() {
+        let best = best(0.2);
+        assert!(best.rotation.steps.is_empty());
+        assert_eq!(best.damage, 0.0);
+        assert!(best.exhaustive);
+    }
+
+    #[test]
+    fn long_windows_are_cut_short() {
+        assert!(!best(30.0).exhaustive);
+    }
+
+    #[test]
+    fn abilities_are_recast_once_off_cooldown() {
+        // Light Binding has an 11 second cooldown at rank 1, 5.5 seconds with 100 haste
+        let ranks = AbilityRanks::new(1, 0, 0, 0);
+        let mut player = player();
+        assert_eq!(casts(&best_with(&player, &ranks, 11.0), Ability::Q), 1);
+        assert_eq!(casts(&best_with(&player, &ranks, 11.5), Ability::Q), 2);
+        player.champion_stats.ability_haste = 100.0;
+        assert_eq!(casts(&best_with(&player, &ranks, 11.5), Ability::Q), 3);
+    }
+
+    #[test]
+    fn abilities_are_only_cast_with_enough_mana() {
+        // Light Binding costs 50 mana and Lucent Singularity 70 at rank 1
+        let ranks = AbilityRanks::new(1, 0, 1, 0);
+        let mut player = player();
+        player.champion_stats.resource_value = 100.0;
+        let best = best_with(&player, &ranks, 3.0);
+        assert_eq!(casts(&best, Ability::Q) + casts(&best, Ability::E), 1);
+        // Regenerating 10 mana a second pays for the second ability after 2 seconds, which
+        // auto attacks fill
+        player.champion_stats.resource_regen_rate = 10.0;
+        let best = best_with(&player, &ranks, 3.0);
+        assert_eq!(casts(&best, Ability::Q) + casts(&best, Ability::E), 1);
+        let best = best_with(&player, &ranks, 4.0);
+        assert_eq!(casts(&best, Ability::Q) + casts(&best, Ability::E), 2);
+    }
+}
@@ -0,0 +1,480 @@
+use std::{fmt, str::FromStr};
+
+use crate::champions::Ability;
+
+// Most steps a rotation can expand to, keeping repeats from allocating without bound
+const MAX_STEPS: usize = 100;
+
+// A rotation written as a sequence of steps, for example "QW(EA)*2R@3S1?"
+//
+// Steps:
+//   Q W E R P A   champion abilities, P for the passive and A for an auto attack
+//   S1 S2         first or second summoner spell
+//   I0 - I6       active of the item in the given inventory slot
+//   ( ... )       a group of steps, used to repeat auto attack weaving
+//
+// Each step can be followed by modifiers:
+//   *N            repeat the step N times, up to MAX_STEPS steps in total
+//   @N            the step hits N targets
+//   ?             the step is only used if it's available
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rotation {
+    pub steps: Vec<Step>,
+}
+
+// A rotation with the name it's shown under in the burst table
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamedRotation {
+    pub name: String,
+    pub rotation: Rotation,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Step {
+    pub action: Action,
+    // Enemies the step hits when given with @N, otherwise only the main target outside of
+    // teamfights
+    pub targets: Option<u32>,
+    pub optional: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Ability(Ability),
+    SummonerSpell(u8),
+    ItemActive(i64),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub position: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid rotation at character {}: {}",
+            self.position + 1,
+            self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl FromStr for Rotation {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            chars: s.chars().collect(),
+            position: 0,
+        };
+        let steps = parser.parse_steps()?;
+        if let Some(c) = parser.peek() {
+            return Err(parser.error(format!("unexpected '{}'", c)));
+        }
+        if steps.is_empty() {
+            return Err(parser.error("rotation is empty".to_string()));
+        }
+        Ok(Rotation { steps })
+    }
+}
+
+// Parses a list of named rotations separated by ';', for example "all-in=QWER;poke=QA"
+pub fn parse_named(s: &str) -> Result<Vec<NamedRotation>, ParseError> {
+    let mut rotations = Vec::new();
+    let mut offset = 0;
+    for entry in s.split(';') {
+        let entry_offset = offset;
+        offset += entry.chars().count() + 1;
+        if entry.trim().is_empty() {
+            continue;
+        }
+        let (name, rotation) = match entry.split_once('=') {
+            Some((name, rotation)) if !name.trim().is_empty() => (name, rotation),
+            _ => {
+                return Err(ParseError {
+                    position: entry_offset,
+                    message: format!("expected name=rotation, found \"{}\"", entry.trim()),
+                })
+            }
+        };
+        let rotation = rotation.parse().map_err(|err: ParseError| ParseError {
+            position: entry_offset + name.chars().count() + 1 + err.position,
+            message: format!("{} in rotation \"{}\"", err.message, name.trim()),
+        })?;
+        rotations.push(NamedRotation {
+            name: name.trim().to_string(),
+            rotation,
+        });
+    }
+    if rotations.is_empty() {
+        return Err(ParseError {
+            position: 0,
+            message: "no rotations configured".to_string(),
+        });
+    }
+    Ok(rotations)
+}
+
+impl fmt::Display for Rotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for step in &self.steps {
+            write!(f, "{}", step)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.action {
+            Action::Ability(Ability::Q) => write!(f, "Q")?,
+            Action::Ability(Ability::W) => write!(f, "W")?,
+            Action::Ability(Ability::E) => write!(f, "E")?,
+            Action::Ability(Ability::R) => write!(f, "R")?,
+            Action::Ability(Ability::Passive) => write!(f, "P")?,
+            Action::Ability(Ability::Attack) => write!(f, "A")?,
+            Action::SummonerSpell(n) => write!(f, "S{}", n)?,
+            Action::ItemActive(slot) => write!(f, "I{}", slot)?,
+        }
+        if let Some(targets) = self.targets {
+            write!(f, "@{}", targets)?;
+        }
+        if self.optional {
+            write!(f, "?")?;
+        }
+        Ok(())
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        self.position += 1;
+        c
+    }
+
+    fn error(&self, message: String) -> ParseError {
+        ParseError {
+            position: self.position.min(self.chars.len()),
+            message,
+        }
+    }
+
+    fn skip_separators(&mut self) {
+        while matches!(self.peek(), Some(c) if c.is_whitespace() || c == ',' || c == '>') {
+            self.position += 1;
+        }
+    }
+
+    // Parses steps until the end of the input or a closing parenthesis
+    fn parse_steps(&mut self) -> Result<Vec<Step>, ParseError> {
+        let mut steps = Vec::new();
+        self.skip_separators();
+        while let Some(c) = self.peek() {
+            if c == ')' {
+                break;
+            }
+            let start = self.position;
+            steps.extend(self.parse_step()?);
+            if steps.len() > MAX_STEPS {
+                self.position = start;
+                return Err(self.error(format!("rotation is longer than {} steps", MAX_STEPS)));
+            }
+            self.skip_separators();
+        }
+        Ok(steps)
+    }
+
+    fn parse_step(&mut self) -> Result<Vec<Step>, ParseError> {
+        let start = self.position;
+        let mut steps = match self.next() {
+            Some('(') => {
+                let steps = self.parse_steps()?;
+                if self.next() != Some(')') {
+                    self.position = start;
+                    return Err(self.error("unclosed '('".to_string()));
+                }
+                if steps.is_empty() {
+                    self.position = start;
+                    return Err(self.error("empty group".to_string()));
+                }
+                steps
+            }
+            Some('S') => match self.parse_number()? {
+                n @ (1 | 2) => vec![Step::new(Action::SummonerSpell(n as u8))],
+                _ => {
+                    self.position = start;
+                    return Err(self.error("summoner spell must be S1 or S2".to_string()));
+                }
+            },
+            Some('I') => match self.parse_number()? {
+                slot @ 0..=6 => vec![Step::new(Action::ItemActive(slot as i64))],
+                _ => {
+                    self.position = start;
+                    return Err(self.error("item slot must be between I0 and I6".to_string()));
+                }
+            },
+            Some(c) => match Ability::from_char(c) {
+                Some(ability) => vec![Step::new(Action::Ability(ability))],
+                None => {
+                    self.position = start;
+                    return Err(self.error(format!("unknown step '{}'", c)));
+                }
+            },
+            None => return Err(self.error("expected a step".to_string())),
+        };
+
+        // Apply the modifiers to every step of the atom
+        let mut repeat = 1;
+        loop {
+            match self.peek() {
+                Some('*') => {
+                    self.position += 1;
+                    let number = self.position;
+                    repeat = self.parse_number()?;
+                    if repeat == 0 {
+                        return Err(self.error("repeat count must be at least 1".to_string()));
+                    }
+                    if steps.len() * repeat as usize > MAX_STEPS {
+                        self.position = number;
+                        return Err(
+                            self.error(format!("rotation is longer than {} steps", MAX_STEPS))
+                        );
+                    }
+                }
+                Some('@') => {
+                    self.position += 1;
+                    let targets = self.parse_number()?;
+                    if targets == 0 {
+                        return Err(self.error("target count must be at least 1".to_string()));
+                    }
+                    steps.iter_mut().for_each(|s| s.targets = Some(targets));
+                }
+                Some('?') => {
+                    self.position += 1;
+                    steps.iter_mut().for_each(|s| s.optional = true);
+                }
+                _ => break,
+            }
+        }
+
+        let mut repeated = Vec::new();
+        for _ in 0..repeat {
+            repeated.extend(steps.iter().copied());
+        }
+        Ok(repeated)
+    }
+
+    fn parse_number(&mut self) -> Result<u32, ParseError> {
+        let start = self.position;
+        while matches!(self.peek(), Some(c) if c.is_ascii_digit()) {
+            self.position += 1;
+        }
+        if start == self.position {
+            return Err(self.error("expected a number".to_string()));
+        }
+        let digits: String = self.chars[start..self.position].iter().collect();
+        digits.parse().map_err(|_| ParseError {
+            position: start,
+            message: format!("{} is too large", digits),
+        })
+    }
+}
+
+impl Step {
+    fn new(action: Action) -> Self {
+        Step {
+            action,
+            targets: None,
+            optional: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Rotation {
+        s.parse().expect("Invalid rotation")
+    }
+
+    fn error(s: &str) -> (usize, String) {
+        let err = s
+            .parse::<Rotation>()
+            .expect_err("Rotation should be invalid");
+        (err.position, err.message)
+    }
+
+    fn ability(ability: Ability) -> Step {
+        Step::new(Action::Ability(ability))
+    }
+
+    #[test]
+    fn steps_are_parsed_in_order() {
+        assert_eq!(
+            parse("QWERPA").steps,
+            [
+                Ability::Q,
+                Ability::W,
+                Ability::E,
+                Ability::R,
+                Ability::Passive,
+                Ability::Attack,
+            ]
+            .map(ability)
+        );
+        assert_eq!(
+            parse("S1S2I0I3").steps,
+            vec![
+                Step::new(Action::SummonerSpell(1)),
+                Step::new(Action::SummonerSpell(2)),
+                Step::new(Action::ItemActive(0)),
+                Step::new(Action::ItemActive(3)),
+            ]
+        );
+    }
+
+    #[test]
+    fn separators_are_ignored() {
+        assert_eq!(parse(" Q, W > E\tR ").steps, parse("QWER").steps);
+        assert_eq!(parse("(E, A)*2").steps, parse("(EA)*2").steps);
+    }
+
+    #[test]
+    fn repeats_apply_to_steps_and_groups() {
+        assert_eq!(parse("Q*2").steps, vec![ability(Ability::Q); 2]);
+        assert_eq!(
+            parse("(EA)*2").steps,
+            [Ability::E, Ability::Attack, Ability::E, Ability::Attack].map(ability)
+        );
+        assert_eq!(parse("((QA)*2R)*2").steps.len(), 10);
+    }
+
+    #[test]
+    fn modifiers_apply_to_every_step_of_a_group() {
+        let steps = parse("(QW)@3?E").steps;
+        for step in &steps[..2] {
+            assert_eq!(step.targets, Some(3));
+            assert!(step.optional);
+        }
+        assert_eq!(steps[2], ability(Ability::E));
+        assert_eq!(parse("Q@1").steps[0].targets, Some(1));
+        assert_eq!(parse("Q").steps[0].targets, None);
+    }
+
+    #[test]
+    fn rotations_display_as_they_are_parsed() {
+        for rotation in ["QW@2E?R", "AS1I3", "QQQ"] {
+            assert_eq!(parse(rotation).to_string(), rotation);
+        }
+        assert_eq!(parse("(EA)*2 > R@1").to_string(), "EAEAR@1");
+    }
+
+    #[test]
+    fn invalid_steps_are_reported_where_they_start() {
+        assert_eq!(error("QX"), (1, "unknown step 'X'".to_string()));
+        assert_eq!(
+            error("QS3"),
+            (1, "summoner spell must be S1 or S2".to_string())
+        );
+        assert_eq!(
+            error("I7"),
+            (0, "item slot must be between I0 and I6".to_string())
+        );
+        assert_eq!(error("Q(WE"), (1, "unclosed '('".to_string()));
+        assert_eq!(error("Q()"), (1, "empty group".to_string()));
+        assert_eq!(error("QW)"), (2, "unexpected ')'".to_string()));
+    }
+
+    #[test]
+    fn invalid_modifiers_are_reported_at_their_number() {
+        assert_eq!(error("Q*"), (2, "expected a number".to_string()));
+        assert_eq!(error("S"), (1, "expected a number".to_string()));
+        assert_eq!(
+            error("Q*0"),
+            (3, "repeat count must be at least 1".to_string())
+        );
+        assert_eq!(
+            error("Q@0"),
+            (3, "target count must be at least 1".to_string())
+        );
+        assert_eq!(
+            error("Q@99999999999"),
+            (2, "99999999999 is too large".to_string())
+        );
+    }
+
+    #[test]
+    fn repeats_are_capped() {
+        assert_eq!(parse("A*100").steps.len(), 100);
+        assert_eq!(
+            error("Q*4000000000"),
+            (2, "rotation is longer than 100 steps".to_string())
+        );
+        assert_eq!(
+            error("(QW)*51"),
+            (5, "rotation is longer than 100 steps".to_string())
+        );
+        assert_eq!(
+            error("A*100Q"),
+            (5, "rotation is longer than 100 steps".to_string())
+        );
+        assert_eq!(
+            error("((Q*10)*10)*2"),
+            (12, "rotation is longer than 100 steps".to_string())
+        );
+    }
+
+    #[test]
+    fn empty_rotations_are_rejected() {
+        assert_eq!(error(""), (0, "rotation is empty".to_string()));
+        assert_eq!(error(" , > "), (5, "rotation is empty".to_string()));
+    }
+
+    #[test]
+    fn errors_display_one_based_positions() {
+        assert_eq!(
+            "QX".parse::<Rotation>().unwrap_err().to_string(),
+            "Invalid rotation at character 2: unknown step 'X'"
+        );
+    }
+
+    #[test]
+    fn named_rotations_are_split_and_trimmed() {
+        let rotations = parse_named(" all-in = QWER ;poke=QA;").unwrap();
+        assert_eq!(rotations.len(), 2);
+        assert_eq!(rotations[0].name, "all-in");
+        assert_eq!(rotations[0].rotation, parse("QWER"));
+        assert_eq!(rotations[1].name, "poke");
+        assert_eq!(rotations[1].rotation, parse("QA"));
+    }
+
+    #[test]
+    fn named_rotation_errors_are_offset_into_the_whole_list() {
+        let err = parse_named("all-in=QWER;poke=QX").unwrap_err();
+        assert_eq!(err.position, 18);
+        assert_eq!(err.message, "unknown step 'X' in rotation \"poke\"");
+        let err = parse_named("a=Q;b=W;c=E*0").unwrap_err();
+        assert_eq!(err.position, 13);
+        let err = parse_named("a=Q;QW").unwrap_err();
+        assert_eq!(err.position, 4);
+        assert_eq!(err.message, "expected name=rotation, found \"QW\"");
+        let err = parse_named(" ; ").unwrap_err();
+        assert_eq!(err.message, "no rotations configured");
+    }
+}
@@ -0,0 +1,412 @@
+use crate::{
+    active_player,
+    champions::definition::ChampionDefinition,
+    dmg::{Damage, DamageType},
+    utils::stats::by_level,
+};
+
+const ELECTROCUTE: i64 = 8112;
+const DARK_HARVEST: i64 = 8128;
+const SUMMON_AERY: i64 = 8214;
+const ARCANE_COMET: i64 = 8229;
+const PRESS_THE_ATTACK: i64 = 8005;
+const CONQUEROR: i64 = 8010;
+const FIRST_STRIKE: i64 = 8369;
+const SCORCH: i64 = 8237;
+const CHEAP_SHOT: i64 = 8126;
+const COUP_DE_GRACE: i64 = 8014;
+const CUT_DOWN: i64 = 8017;
+const LAST_STAND: i64 = 8299;
+
+const CONQUEROR_MAX_STACKS: u32 = 12;
+
+// The kind of hit a rune can proc on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hit {
+    Attack,
+    Ability,
+}
+
+// Ids of the active player's keystone and the other runes they took
+#[derive(Debug, Clone, Default)]
+pub struct Runes {
+    pub keystone: i64,
+    pub general: Vec<i64>,
+}
+
+impl Runes {
+    pub fn new(active_player: &active_player::Root) -> Self {
+        let full_runes = &active_player.full_runes;
+        Runes {
+            keystone: full_runes.keystone.id,
+            general: full_runes.general_runes.iter().map(|r| r.id).collect(),
+        }
+    }
+
+    fn has(&self, id: i64) -> bool {
+        self.keystone == id || self.general.contains(&id)
+    }
+}
+
+// Adaptive damage is physical when the champion has more bonus AD than AP
+fn adaptive_type(bonus_attack_damage: f64, ability_power: f64) -> DamageType {
+    if bonus_attack_damage > ability_power {
+        DamageType::Physical
+    } else {
+        DamageType::Magic
+    }
+}
+
+// The active player's stats with Conqueror fully stacked, None without Conqueror
+pub fn conqueror_player(
+    runes: &Runes,
+    definition: &ChampionDefinition,
+    active_player: &active_player::Root,
+) -> Option<active_player::Root> {
+    if runes.keystone != CONQUEROR {
+        return None;
+    }
+    let mut stacked = active_player.clone();
+    let stats = &mut stacked.champion_stats;
+    let adaptive_force = by_level(2.0, 4.5, active_player.level) * CONQUEROR_MAX_STACKS as f64;
+    // One adaptive force is worth 0.6 AD or 1 AP
+    match adaptive_type(
+        definition.bonus_attack_damage(active_player),
+        stats.ability_power,
+    ) {
+        DamageType::Physical => stats.attack_damage += 0.6 * adaptive_force,
+        _ => stats.ability_power += adaptive_force,
+    }
+    Some(stacked)
+}
+
+// Hits and procs counted so far, which decide what later hits proc
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ProcState {
+    hits: u32,
+    ability_hits: u32,
+    consecutive_attacks: u32,
+    conqueror_stacks: u32,
+    keystone_used: bool,
+    scorch_used: bool,
+    cheap_shot_used: bool,
+    exposed: bool,
+}
+
+// Tracks which runes have procced over the steps of a single rotation against one target
+#[derive(Clone)]
+pub struct Procs<'a> {
+    runes: &'a Runes,
+    active_player: &'a active_player::Root,
+    adaptive_type: DamageType,
+    bonus_attack_damage: f64,
+    target_max_health: f64,
+    ranged: bool,
+    state: ProcState,
+    // Amplification from runes that don't depend on the rotation
+    amplifier: f64,
+}
+
+impl<'a> Procs<'a> {
+    pub fn new(
+        runes: &'a Runes,
+        definition: &ChampionDefinition,
+        active_player: &'a active_player::Root,
+        target_max_health: f64,
+    ) -> Self {
+        let stats = &active_player.champion_stats;
+        let bonus_attack_damage = definition.bonus_attack_damage(active_player);
+
+        // First Strike is assumed to start every burst, adding 7% of the damage as true damage
+        let mut amplifier = if runes.keystone == FIRST_STRIKE {
+            1.07
+        } else {
+            1.0
+        };
+        // Cut Down scales from 5% against 10% more max health up to 15% against double
+        if runes.has(CUT_DOWN) && stats.max_health > 0.0 {
+            let ratio = target_max_health / stats.max_health;
+            if ratio >= 1.1 {
+                amplifier *= 1.05 + 0.1 * ((ratio - 1.1) / 0.9).min(1.0);
+            }
+        }
+        // Last Stand scales from 5% below 60% health up to 11% below 30%
+        if runes.has(LAST_STAND) && stats.max_health > 0.0 {
+            let health = stats.current_health / stats.max_health;
+            if health < 0.6 {
+                amplifier *= 1.05 + 0.06 * ((0.6 - health) / 0.3).min(1.0);
+            }
+        }
+
+        Procs {
+            runes,
+            active_player,
+            adaptive_type: adaptive_type(bonus_attack_damage, stats.ability_power),
+            bonus_attack_damage,
+            target_max_health,
+            ranged: stats.attack_range > 300.0,
+            state: ProcState::default(),
+            amplifier,
+        }
+    }
+
+    pub fn state(&self) -> ProcState {
+        self.state
+    }
+
+    // Share of Conqueror's full stacks built up before the next hit
+    pub fn conqueror_stacks(&self) -> f64 {
+        self.state.conqueror_stacks as f64 / CONQUEROR_MAX_STACKS as f64
+    }
+
+    // Bonus damage from runes proccing on the hit, health is the target's health before the hit
+    pub fn hit(&mut self, hit: Hit, health: f64) -> Damage {
+        let level = self.active_player.level;
+        let ability_power = self.active_player.champion_stats.ability_power;
+        let mut damage = Damage::default();
+
+        self.state.hits += 1;
+        match hit {
+            Hit::Attack => self.state.consecutive_attacks += 1,
+            Hit::Ability => {
+                self.state.ability_hits += 1;
+                self.state.consecutive_attacks = 0;
+            }
+        }
+        let stacks = if self.ranged { 1 } else { 2 };
+        self.state.conqueror_stacks =
+            (self.state.conqueror_stacks + stacks).min(CONQUEROR_MAX_STACKS);
+
+        if !self.state.keystone_used {
+            let keystone = match self.runes.keystone {
+                // Procs on the third separate attack or ability
+                ELECTROCUTE if self.state.hits == 3 => Some(
+                    by_level(30.0, 180.0, level)
+                        + 0.4 * self.bonus_attack_damage
+                        + 0.25 * ability_power,
+                ),
+                // Procs once the target drops below half health
+                DARK_HARVEST if health <= self.target_max_health / 2.0 => Some(
+                    by_level(20.0, 60.0, level)
+                        + 0.25 * self.bonus_attack_damage
+                        + 0.15 * ability_power,
+                ),
+                SUMMON_AERY => Some(
+                    by_level(10.0, 50.0, level)
+                        + 0.1 * self.bonus_attack_damage
+                        + 0.05 * ability_power,
+                ),
+                ARCANE_COMET if hit == Hit::Ability => Some(
+                    by_level(30.0, 100.0, level)
+                        + 0.35 * self.bonus_attack_damage
+                        + 0.2 * ability_power,
+                ),
+                // Procs on the third consecutive attack and exposes the target
+                PRESS_THE_ATTACK if self.state.consecutive_attacks == 3 => {
+                    self.state.exposed = true;
+                    Some(by_level(40.0, 180.0, level))
+                }
+                _ => None,
+            };
+            if let Some(amount) = keystone {
+                self.state.keystone_used = true;
+                damage += Damage::new(amount, self.adaptive_type);
+            }
+        }
+
+        if self.runes.has(SCORCH) && !self.state.scorch_used && hit == Hit::Ability {
+            self.state.scorch_used = true;
+            damage += Damage::new(by_level(20.0, 40.0, level), DamageType::Magic);
+        }
+        // The target is assumed to be impaired by the first ability that hits them
+        if self.runes.has(CHEAP_SHOT) && !self.state.cheap_shot_used && self.state.ability_hits > 0
+        {
+            self.state.cheap_shot_used = true;
+            damage += Damage::new(by_level(10.0, 45.0, level), DamageType::True);
+        }
+
+        damage
+    }
+
+    // Multiplier applied to the mitigated damage of the next hit against a target with the
+    // given health
+    pub fn amplifier(&self, health: f64) -> f64 {
+        let mut amplifier = self.amplifier;
+        if self.state.exposed {
+            amplifier *= 1.08;
+        }
+        if self.runes.has(COUP_DE_GRACE) && health < 0.4 * self.target_max_health {
+            amplifier *= 1.08;
+        }
+        amplifier
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::champions::definition;
+
+    fn lux() -> ChampionDefinition {
+        definition::load_definitions("./resources/champions").unwrap()["Lux"].clone()
+    }
+
+    // A level 1 Lux with ability power and no bonus attack damage
+    fn player(ability_power: f64, attack_range: f64) -> active_player::Root {
+        let mut player = active_player::Root {
+            level: 1,
+            ..Default::default()
+        };
+        let stats = &mut player.champion_stats;
+        stats.ability_power = ability_power;
+        stats.attack_damage = lux().base_stats.attack_damage;
+        stats.attack_range = attack_range;
+        stats.max_health = 1000.0;
+        stats.current_health = 1000.0;
+        player
+    }
+
+    fn runes(keystone: i64, general: &[i64]) -> Runes {
+        Runes {
+            keystone,
+            general: general.to_vec(),
+        }
+    }
+
+    // Total damage of the runes over the hits against a target at full health
+    fn procs(runes: &Runes, hits: &[Hit]) -> Vec<f64> {
+        let (definition, player) = (lux(), player(100.0, 550.0));
+        let mut procs = Procs::new(runes, &definition, &player, 2000.0);
+        hits.iter()
+            .map(|hit| procs.hit(*hit, 2000.0).total())
+            .collect()
+    }
+
+    #[test]
+    fn electrocute_procs_on_the_third_hit() {
+        let runes = runes(ELECTROCUTE, &[]);
+        assert_eq!(
+            procs(
+                &runes,
+                &[Hit::Ability, Hit::Attack, Hit::Ability, Hit::Ability]
+            ),
+            [0.0, 0.0, 30.0 + 25.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn arcane_comet_only_procs_on_abilities() {
+        let runes = runes(ARCANE_COMET, &[]);
+        assert_eq!(
+            procs(&runes, &[Hit::Attack, Hit::Ability, Hit::Ability]),
+            [0.0, 30.0 + 20.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn press_the_attack_procs_on_three_consecutive_attacks() {
+        let runes = runes(PRESS_THE_ATTACK, &[]);
+        let (definition, player) = (lux(), player(100.0, 550.0));
+        let mut procs = Procs::new(&runes, &definition, &player, 2000.0);
+        // An ability in between starts the count over
+        for hit in [
+            Hit::Attack,
+            Hit::Attack,
+            Hit::Ability,
+            Hit::Attack,
+            Hit::Attack,
+        ] {
+            assert_eq!(procs.hit(hit, 2000.0).total(), 0.0);
+            assert_eq!(procs.amplifier(2000.0), 1.0);
+        }
+        assert_eq!(procs.hit(Hit::Attack, 2000.0).total(), 40.0);
+        // The exposed target takes more damage from every later step
+        assert_eq!(procs.amplifier(2000.0), 1.08);
+        assert_eq!(procs.hit(Hit::Ability, 2000.0).total(), 0.0);
+        assert_eq!(procs.amplifier(2000.0), 1.08);
+    }
+
+    #[test]
+    fn dark_harvest_procs_below_half_health() {
+        let runes = runes(DARK_HARVEST, &[]);
+        let (definition, player) = (lux(), player(100.0, 550.0));
+        let mut procs = Procs::new(&runes, &definition, &player, 2000.0);
+        assert_eq!(procs.hit(Hit::Ability, 1001.0).total(), 0.0);
+        assert_eq!(procs.hit(Hit::Ability, 1000.0).total(), 20.0 + 15.0);
+        assert_eq!(procs.hit(Hit::Ability, 500.0).total(), 0.0);
+    }
+
+    #[test]
+    fn conqueror_stacks_faster_for_melee_champions() {
+        let runes = runes(CONQUEROR, &[]);
+        let definition = lux();
+        for (attack_range, stacks) in [(550.0, 1), (125.0, 2)] {
+            let player = player(100.0, attack_range);
+            let mut procs = Procs::new(&runes, &definition, &player, 2000.0);
+            assert_eq!(procs.conqueror_stacks(), 0.0);
+            procs.hit(Hit::Ability, 2000.0);
+            assert_eq!(
+                procs.conqueror_stacks(),
+                stacks as f64 / CONQUEROR_MAX_STACKS as f64
+            );
+            for _ in 0..CONQUEROR_MAX_STACKS {
+                procs.hit(Hit::Attack, 2000.0);
+            }
+            assert_eq!(procs.conqueror_stacks(), 1.0);
+        }
+    }
+
+    #[test]
+    fn conqueror_fully_stacked_adds_adaptive_force() {
+        let definition = lux();
+        let player = player(100.0, 550.0);
+        let stacked = conqueror_player(&runes(CONQUEROR, &[]), &definition, &player).unwrap();
+        assert_eq!(stacked.champion_stats.ability_power, 100.0 + 2.0 * 12.0);
+        assert!(conqueror_player(&runes(ELECTROCUTE, &[]), &definition, &player).is_none());
+    }
+
+    #[test]
+    fn cut_down_scales_with_the_targets_extra_max_health() {
+        let runes = runes(0, &[CUT_DOWN]);
+        let (definition, player) = (lux(), player(100.0, 550.0));
+        let amplifier = |target_max_health| {
+            Procs::new(&runes, &definition, &player, target_max_health).amplifier(0.0)
+        };
+        for (target_max_health, expected) in [
+            (1050.0, 1.0),
+            (1100.0, 1.05),
+            (1550.0, 1.1),
+            (2000.0, 1.15),
+            (3000.0, 1.15),
+        ] {
+            assert!((amplifier(target_max_health) - expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn last_stand_scales_with_the_players_missing_health() {
+        let runes = runes(0, &[LAST_STAND]);
+        let definition = lux();
+        for (current_health, expected) in [
+            (700.0, 1.0),
+            (600.0, 1.0),
+            (500.0, 1.07),
+            (300.0, 1.11),
+            (100.0, 1.11),
+        ] {
+            let mut player = player(100.0, 550.0);
+            player.champion_stats.current_health = current_health;
+            let amplifier = Procs::new(&runes, &definition, &player, 1000.0).amplifier(1000.0);
+            assert!((amplifier - expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn coup_de_grace_amplifies_damage_below_40_percent_health() {
+        let runes = runes(0, &[COUP_DE_GRACE]);
+        let (definition, player) = (lux(), player(100.0, 550.0));
+        let procs = Procs::new(&runes, &definition, &player, 2000.0);
+        assert_eq!(procs.amplifier(800.0), 1.0);
+        assert_eq!(procs.amplifier(799.0), 1.08);
+    }
+}
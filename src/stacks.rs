@@ -0,0 +1,181 @@
+use serde_json::Value;
+
+use crate::{active_player, all_players, champions::definition::ChampionDefinition, utils::items};
+
+// The stat a champion's stacks grant, used to count the stacks from the stat growing
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StackSource {
+    // Each stack grants the given ability power
+    AbilityPower(f64),
+    // Each stack grants the given attack damage
+    AttackDamage(f64),
+    // Each stack grants the given max health
+    Health(f64),
+    // Stacks don't show up in any stat and have to be entered by the user
+    Manual,
+}
+
+// Stacks of the active player's champion, counted across ticks or entered by the user
+#[derive(Debug, Clone, Default)]
+pub struct Stacks {
+    pub source: Option<StackSource>,
+    pub count: u32,
+    // Stat growth that doesn't add up to a whole stack yet
+    remainder: f64,
+    // Highest stat so far that isn't explained by levels or items
+    last_stat: Option<f64>,
+}
+
+impl Stacks {
+    pub fn new(source: Option<StackSource>) -> Self {
+        Stacks {
+            source,
+            ..Default::default()
+        }
+    }
+
+    // Counts the stacks gained since the last tick from the growth of the stat they grant.
+    // Growth from levels and items is taken out first, stat bonuses that don't change
+    // between ticks like rune shards cancel out, and stats dropping is never counted as
+    // stacks being lost, nor is growing back to where it was. Stacks gained before the first
+    // tick have to be entered by the user.
+    // Percent ability power items like Rabadon's Deathcap aren't taken out, as Data Dragon
+    // only has their flat stats. While one is held every AP delta is multiplied by it, so
+    // stacks are overcounted, and buying one counts the amplified AP as stacks.
+    pub fn update(
+        &mut self,
+        active_player: &active_player::Root,
+        definition: &ChampionDefinition,
+        items: &[all_players::Item],
+        ddragon_items: &Value,
+    ) {
+        let item_stat = |stat| items::item_stat(ddragon_items, items, stat);
+        let (stat, per_stack) = match self.source {
+            Some(StackSource::AbilityPower(per_stack)) => (
+                active_player.champion_stats.ability_power - item_stat("FlatMagicDamageMod"),
+                per_stack,
+            ),
+            Some(StackSource::AttackDamage(per_stack)) => (
+                definition.bonus_attack_damage(active_player) - item_stat("FlatPhysicalDamageMod"),
+                per_stack,
+            ),
+            Some(StackSource::Health(per_stack)) => (
+                definition.bonus_health(active_player) - item_stat("FlatHPPoolMod"),
+                per_stack,
+            ),
+            Some(StackSource::Manual) | None => return,
+        };
+        if let Some(last_stat) = self.last_stat {
+            self.remainder += (stat - last_stat).max(0.0) / per_stack;
+            let gained = self.remainder.floor();
+            self.count += gained as u32;
+            self.remainder -= gained;
+            if gained > 0.0 {
+                debug!("Gained {} stacks, {} in total", gained, self.count);
+            }
+        }
+        self.last_stat = Some(self.last_stat.map_or(stat, |last_stat| last_stat.max(stat)));
+    }
+
+    // Replaces the count with stacks entered by the user
+    pub fn set(&mut self, count: u32) {
+        info!("Stacks set to {}", count);
+        self.count = count;
+        self.remainder = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::champions::definition;
+    use std::fs;
+
+    fn ddragon_items() -> Value {
+        serde_json::from_str(
+            &fs::read_to_string("./resources/fixtures/ddragon/12.13.1/item.json")
+                .expect("Failed to read string from file"),
+        )
+        .expect("Failed to deserialize String into JSON Value")
+    }
+
+    fn veigar() -> ChampionDefinition {
+        definition::load_definitions("./resources/champions").unwrap()["Veigar"].clone()
+    }
+
+    fn player(ability_power: f64) -> active_player::Root {
+        let mut player = active_player::Root {
+            level: 6,
+            ..Default::default()
+        };
+        player.champion_stats.ability_power = ability_power;
+        player
+    }
+
+    fn item(item_id: i64) -> all_players::Item {
+        all_players::Item {
+            item_id,
+            count: 1,
+            ..Default::default()
+        }
+    }
+
+    // Updates the stacks with a tick for each ability power and its items
+    fn ticks(stacks: &mut Stacks, ticks: &[(f64, Vec<all_players::Item>)]) {
+        let (definition, ddragon_items) = (veigar(), ddragon_items());
+        for (ability_power, items) in ticks {
+            stacks.update(&player(*ability_power), &definition, items, &ddragon_items);
+        }
+    }
+
+    #[test]
+    fn item_stats_are_not_counted_as_stacks() {
+        let mut stacks = Stacks::new(Some(StackSource::AbilityPower(1.0)));
+        // An Amplifying Tome is bought along with 3 stacks, then a Needlessly Large Rod alone
+        ticks(
+            &mut stacks,
+            &[
+                (50.0, vec![]),
+                (73.0, vec![item(1052)]),
+                (133.0, vec![item(1052), item(1058)]),
+            ],
+        );
+        assert_eq!(stacks.count, 3);
+    }
+
+    #[test]
+    fn partial_stacks_carry_over_to_later_ticks() {
+        let mut stacks = Stacks::new(Some(StackSource::AbilityPower(4.0)));
+        ticks(&mut stacks, &[(50.0, vec![]), (52.0, vec![])]);
+        assert_eq!(stacks.count, 0);
+        ticks(&mut stacks, &[(55.0, vec![])]);
+        assert_eq!(stacks.count, 1);
+        ticks(&mut stacks, &[(58.0, vec![])]);
+        assert_eq!(stacks.count, 2);
+    }
+
+    #[test]
+    fn stat_drops_are_not_counted() {
+        let mut stacks = Stacks::new(Some(StackSource::AbilityPower(1.0)));
+        // Selling a Needlessly Large Rod, then the AP dropping and growing back
+        ticks(
+            &mut stacks,
+            &[
+                (110.0, vec![item(1058)]),
+                (50.0, vec![]),
+                (30.0, vec![]),
+                (52.0, vec![]),
+            ],
+        );
+        assert_eq!(stacks.count, 2);
+    }
+
+    #[test]
+    fn entered_stacks_replace_the_count() {
+        let mut stacks = Stacks::new(Some(StackSource::AbilityPower(4.0)));
+        ticks(&mut stacks, &[(50.0, vec![]), (56.0, vec![])]);
+        stacks.set(40);
+        ticks(&mut stacks, &[(58.0, vec![])]);
+        assert_eq!(stacks.count, 40);
+    }
+}
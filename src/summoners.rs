@@ -0,0 +1,195 @@
+use crate::{
+    active_player, all_players,
+    dmg::{Damage, DamageType},
+    rotation::{Action, Rotation, Step},
+};
+
+// Summoner spells that deal damage to champions or keep them alive through a burst, the rest
+// are treated as utility
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SummonerSpell {
+    Ignite,
+    ChillingSmite,
+    ChallengingSmite,
+    Heal,
+    Barrier,
+    Other,
+}
+
+impl SummonerSpell {
+    // Matches the Live Client's raw display name, which unlike the display name isn't localized
+    pub fn from_raw_display_name(raw_display_name: &str) -> Self {
+        let name = raw_display_name
+            .trim_start_matches("GeneratedTip_SummonerSpell_")
+            .trim_end_matches("_DisplayName");
+        match name {
+            "SummonerDot" => SummonerSpell::Ignite,
+            "S5_SummonerSmitePlayerGanker" => SummonerSpell::ChillingSmite,
+            "S5_SummonerSmiteDuel" => SummonerSpell::ChallengingSmite,
+            "SummonerHeal" => SummonerSpell::Heal,
+            "SummonerBarrier" => SummonerSpell::Barrier,
+            _ => SummonerSpell::Other,
+        }
+    }
+
+    // The player's first and second summoner spell
+    pub fn from_player(player: &all_players::AllPlayer) -> [SummonerSpell; 2] {
+        let spells = &player.summoner_spells;
+        [
+            SummonerSpell::from_raw_display_name(&spells.summoner_spell_one.raw_display_name),
+            SummonerSpell::from_raw_display_name(&spells.summoner_spell_two.raw_display_name),
+        ]
+    }
+
+    // Damage dealt to a champion by one cast, damage over time is counted in full
+    pub fn damage(&self, active_player: &active_player::Root) -> Damage {
+        let level = active_player.level as f64;
+        match self {
+            SummonerSpell::Ignite => Damage::new(50.0 + 20.0 * level, DamageType::True),
+            SummonerSpell::ChillingSmite => Damage::new(20.0 + 8.0 * level, DamageType::True),
+            // Challenging Smite's mark adds true damage to each auto attack for a few seconds.
+            // The spell is usually cast at the end of the burst, so one proc is counted.
+            SummonerSpell::ChallengingSmite => {
+                Damage::new(20.0 + 2.0 * (level - 1.0), DamageType::True)
+            }
+            SummonerSpell::Heal | SummonerSpell::Barrier | SummonerSpell::Other => {
+                Damage::default()
+            }
+        }
+    }
+
+    pub fn deals_damage(&self) -> bool {
+        matches!(
+            self,
+            SummonerSpell::Ignite | SummonerSpell::ChillingSmite | SummonerSpell::ChallengingSmite
+        )
+    }
+}
+
+// Adds the equipped damaging summoner spells the rotation doesn't already use to its end
+pub fn with_summoner_spells(rotation: &Rotation, summoner_spells: &[SummonerSpell; 2]) -> Rotation {
+    let mut rotation = rotation.clone();
+    for (n, spell) in (1..).zip(summoner_spells) {
+        let used = rotation
+            .steps
+            .iter()
+            .any(|step| step.action == Action::SummonerSpell(n));
+        if spell.deals_damage() && !used {
+            rotation.steps.push(Step {
+                action: Action::SummonerSpell(n),
+                targets: None,
+                optional: false,
+            });
+        }
+    }
+    rotation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player(level: i64) -> active_player::Root {
+        active_player::Root {
+            level,
+            ..Default::default()
+        }
+    }
+
+    fn true_damage(spell: SummonerSpell, level: i64) -> f64 {
+        let damage = spell.damage(&player(level));
+        assert_eq!(damage.total(), damage.true_damage);
+        damage.true_damage
+    }
+
+    #[test]
+    fn ignite_scales_with_level() {
+        assert_eq!(true_damage(SummonerSpell::Ignite, 1), 70.0);
+        assert_eq!(true_damage(SummonerSpell::Ignite, 9), 230.0);
+        assert_eq!(true_damage(SummonerSpell::Ignite, 18), 410.0);
+    }
+
+    #[test]
+    fn smites_scale_with_level() {
+        assert_eq!(true_damage(SummonerSpell::ChillingSmite, 1), 28.0);
+        assert_eq!(true_damage(SummonerSpell::ChillingSmite, 18), 164.0);
+        assert_eq!(true_damage(SummonerSpell::ChallengingSmite, 1), 20.0);
+        assert_eq!(true_damage(SummonerSpell::ChallengingSmite, 18), 54.0);
+    }
+
+    #[test]
+    fn utility_spells_deal_no_damage() {
+        for spell in [
+            SummonerSpell::Heal,
+            SummonerSpell::Barrier,
+            SummonerSpell::Other,
+        ] {
+            assert_eq!(spell.damage(&player(18)), Damage::default());
+            assert!(!spell.deals_damage());
+        }
+    }
+
+    #[test]
+    fn spells_are_parsed_from_raw_display_names() {
+        for (name, spell) in [
+            (
+                "GeneratedTip_SummonerSpell_SummonerDot_DisplayName",
+                SummonerSpell::Ignite,
+            ),
+            (
+                "GeneratedTip_SummonerSpell_S5_SummonerSmitePlayerGanker_DisplayName",
+                SummonerSpell::ChillingSmite,
+            ),
+            (
+                "GeneratedTip_SummonerSpell_S5_SummonerSmiteDuel_DisplayName",
+                SummonerSpell::ChallengingSmite,
+            ),
+            (
+                "GeneratedTip_SummonerSpell_SummonerHeal_DisplayName",
+                SummonerSpell::Heal,
+            ),
+            (
+                "GeneratedTip_SummonerSpell_SummonerBarrier_DisplayName",
+                SummonerSpell::Barrier,
+            ),
+            (
+                "GeneratedTip_SummonerSpell_SummonerFlash_DisplayName",
+                SummonerSpell::Other,
+            ),
+            ("", SummonerSpell::Other),
+        ] {
+            assert_eq!(
+                SummonerSpell::from_raw_display_name(name),
+                spell,
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn damaging_spells_are_added_only_when_equipped() {
+        let rotation: Rotation = "QWE".parse().unwrap();
+        let with = |spells| with_summoner_spells(&rotation, &spells).to_string();
+        assert_eq!(with([SummonerSpell::Other, SummonerSpell::Other]), "QWE");
+        assert_eq!(with([SummonerSpell::Other, SummonerSpell::Ignite]), "QWES2");
+        assert_eq!(
+            with([SummonerSpell::ChallengingSmite, SummonerSpell::Heal]),
+            "QWES1"
+        );
+        assert_eq!(
+            with([SummonerSpell::Ignite, SummonerSpell::ChillingSmite]),
+            "QWES1S2"
+        );
+    }
+
+    #[test]
+    fn spells_already_in_the_rotation_are_not_added_again() {
+        let rotation: Rotation = "S1QWE".parse().unwrap();
+        let spells = [SummonerSpell::Ignite, SummonerSpell::Other];
+        assert_eq!(
+            with_summoner_spells(&rotation, &spells).to_string(),
+            "S1QWE"
+        );
+    }
+}
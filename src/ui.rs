@@ -5,7 +5,7 @@ use tui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     symbols,
-    text::Span,
+    text::{Span, Spans},
     widgets::{Axis, Block, Borders, Cell, Chart, Dataset, GraphType, Paragraph, Row, Table},
     Frame,
 };
@@ -38,10 +38,42 @@ pub fn ui<B: Backend>(f: &mut Frame<B>, size: Rect, app: &mut app::App) {
         .constraints(constraints)
         .split(inner_area);
 
+    // Size the burst table columns to fit their headers and contents
+    let burst_header_names = app.burst_table_header();
+    let burst_widths: Vec<Constraint> = burst_header_names
+        .iter()
+        .enumerate()
+        .map(|(i, h)| match i {
+            0 => Constraint::Length(12),
+            _ => Constraint::Length(
+                app.burst_table_items
+                    .iter()
+                    .filter_map(|row| row.get(i))
+                    .map(|c| c.len())
+                    .chain([h.len(), 5])
+                    .max()
+                    .unwrap_or(5) as u16,
+            ),
+        })
+        .collect();
+    // Column widths plus one column of spacing between each and the borders
+    let burst_table_width = burst_widths
+        .iter()
+        .map(|w| match w {
+            Constraint::Length(l) => *l,
+            _ => 0,
+        })
+        .sum::<u16>()
+        + burst_widths.len() as u16
+        + 1;
+
     // Define a layout for data area
     let data_rects = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints(vec![Constraint::Length(35), Constraint::Percentage(100)])
+        .constraints(vec![
+            Constraint::Length(burst_table_width.max(35)),
+            Constraint::Percentage(100),
+        ])
         .split(rects[0]);
 
     let stats_rects = Layout::default()
@@ -72,16 +104,16 @@ pub fn ui<B: Backend>(f: &mut Frame<B>, size: Rect, app: &mut app::App) {
     // Set the bg style
     let burst_normal_style = Style::default();
     // Set the header cell names and style
-    let burst_header_cells = ["Champion", "Level", "Burst"]
+    let burst_header_cells = burst_header_names
         .iter()
-        .map(|h| Cell::from(*h).style(Style::default().fg(Color::LightBlue)));
+        .map(|h| Cell::from(h.as_str()).style(Style::default().fg(Color::LightBlue)));
     // Set the header row
     let burst_header = Row::new(burst_header_cells)
         .style(burst_normal_style)
         .height(1)
         .bottom_margin(1);
     // Set table rows
-    let burst_rows = app.burst_table_items.iter().map(|item| {
+    let burst_rows = app.burst_table_items.iter().enumerate().map(|(i, item)| {
         let height = item
             .iter()
             .map(|content| content.chars().filter(|c| *c == '\n').count())
@@ -89,18 +121,61 @@ pub fn ui<B: Backend>(f: &mut Frame<B>, size: Rect, app: &mut app::App) {
             .unwrap_or(0)
             + 1;
         let cells = item.iter().map(|c| Cell::from(c.as_str()));
-        Row::new(cells).height(height as u16).bottom_margin(1)
+        // Highlight enemies the burst is predicted to kill
+        let style = if app.burst_table_killable.get(i).copied().unwrap_or(false) {
+            Style::default()
+                .fg(Color::LightGreen)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            burst_normal_style
+        };
+        Row::new(cells)
+            .style(style)
+            .height(height as u16)
+            .bottom_margin(1)
     });
 
+    // Set the burst table title, flagging champions without a burst implementation and
+    // showing the stacks of champions that scale with them. Stacks that only grant stats are
+    // already in the damage, so setting them only corrects the count shown.
+    let burst_title = if let Some(input) = &app.stack_input {
+        Span::styled(
+            format!("burst - stacks: {}_ (Enter to set, Esc to cancel)", input),
+            Style::default().fg(Color::Yellow),
+        )
+    } else if app.stacks.source.is_some() && app.stacks_display_only {
+        Span::raw(format!(
+            "burst - {} stacks (k to set, display only)",
+            app.stacks.count
+        ))
+    } else if app.stacks.source.is_some() {
+        Span::raw(format!("burst - {} stacks (k to set)", app.stacks.count))
+    } else if app.champion_supported {
+        Span::raw("burst")
+    } else {
+        Span::styled(
+            format!("burst - {} not supported", app.champion_name),
+            Style::default().fg(Color::Red),
+        )
+    };
+    // Warn when the champion's damage may be out of date with the game's patch
+    let mut burst_title = vec![burst_title];
+    if let Some(warning) = &app.patch_warning {
+        burst_title.push(Span::styled(
+            format!(" - {}", warning),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+
     // Define the burst table
     let t = Table::new(burst_rows)
         .header(burst_header)
-        .block(Block::default().borders(Borders::ALL).title("burst"))
-        .widths(&[
-            Constraint::Length(12),
-            Constraint::Length(5),
-            Constraint::Length(5),
-        ]);
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(Spans::from(burst_title)),
+        )
+        .widths(&burst_widths);
 
     // Render the burst table
     f.render_stateful_widget(t, data_rects[0], &mut app.burst_table_state);
@@ -117,7 +192,7 @@ pub fn ui<B: Backend>(f: &mut Frame<B>, size: Rect, app: &mut app::App) {
     };
 
     // Set bounds for charts to new Bounds
-    let bounds = app::Bounds::new(&app);
+    let bounds = app::Bounds::new(app);
 
     // Define a layout for "gold per minute"
     // Set style to correct color for "gold per minute"
@@ -1,3 +1,7 @@
 pub mod deserializer;
+pub mod health;
+pub mod items;
 pub mod resistance;
+pub mod shields;
+pub mod stats;
 pub mod teams;
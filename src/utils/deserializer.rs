@@ -1,6 +1,10 @@
 use crate::{active_player, all_players, app::App, game_data, network};
 use reqwest::Client;
-use std::fs;
+use serde_json::Value;
+use std::{fs, time::Duration};
+
+// The game version is only looked up once per game, so a slow client isn't waited on for long
+const GAME_VERSION_TIMEOUT: Duration = Duration::from_secs(5);
 
 pub async fn deserializer(
     app: &App,
@@ -60,3 +64,25 @@ pub async fn deserializer(
 
     (active_player_data, all_player_data, game_data)
 }
+
+// Returns the version of the running game client from the gameData of all game data, None
+// for sample data or when the client doesn't report it
+pub async fn game_version(app: &App, client: &Client) -> Option<String> {
+    if app.use_sample_data {
+        return None;
+    }
+    let all_game_data: Value =
+        network::try_request(client, &app.all_game_data_url, GAME_VERSION_TIMEOUT)
+            .await?
+            .json()
+            .await
+            .ok()?;
+    let version = all_game_data["gameData"]["version"]
+        .as_str()
+        .map(str::to_string);
+    match &version {
+        Some(version) => info!("Game client is on version {}", version),
+        None => warn!("Game client doesn't report its version"),
+    }
+    version
+}
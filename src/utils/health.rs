@@ -0,0 +1,44 @@
+use super::{items, stats, teams::OpponantTeam};
+use crate::{active_player, all_players};
+use serde_json::Value;
+
+// Estimated max health of each opponant, in the same order as OpponantTeam. None for
+// champions missing from the loaded Data Dragon.
+pub struct Health {
+    pub max_health: Vec<Option<f64>>,
+}
+
+impl Health {
+    pub fn new(
+        active_player: &active_player::Root,
+        all_players: &all_players::Root,
+        ddragon_champions: &Value,
+        ddragon_items: &Value,
+    ) -> Self {
+        let opponant_team = OpponantTeam::new(active_player, all_players);
+        let mut max_health = Vec::new();
+        for i in 0..opponant_team.opponants.len() {
+            let champion_name =
+                &stats::champion_id(ddragon_champions, &opponant_team.opponants[i].0);
+            let level = opponant_team.opponants[i].1;
+            let missing_items = items::missing_items(ddragon_items, &opponant_team.items[i]);
+            if !missing_items.is_empty() {
+                warn!(
+                    "Items {:?} of {} are missing from Data Dragon and are ignored",
+                    missing_items, champion_name
+                );
+            }
+            let base_health = stats::scaled_stat(ddragon_champions, champion_name, "hp", level);
+            if base_health.is_none() {
+                warn!(
+                    "{} is missing from Data Dragon and is skipped",
+                    champion_name
+                );
+            }
+            let item_health =
+                items::item_stat(ddragon_items, &opponant_team.items[i], "FlatHPPoolMod");
+            max_health.push(base_health.map(|base_health| base_health + item_health));
+        }
+        Health { max_health }
+    }
+}
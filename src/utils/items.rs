@@ -0,0 +1,25 @@
+use crate::all_players;
+use serde_json::Value;
+
+// Sums a Data Dragon item stat over the items, counting stacked items once per count.
+// Items missing from Data Dragon or without the stat add nothing.
+pub fn item_stat(ddragon_items: &Value, items: &[all_players::Item], stat: &str) -> f64 {
+    items
+        .iter()
+        .map(|item| {
+            ddragon_items["data"][item.item_id.to_string()]["stats"][stat]
+                .as_f64()
+                .unwrap_or(0.0)
+                * item.count as f64
+        })
+        .sum()
+}
+
+// Returns the ids of the items missing from Data Dragon
+pub fn missing_items(ddragon_items: &Value, items: &[all_players::Item]) -> Vec<i64> {
+    items
+        .iter()
+        .filter(|item| ddragon_items["data"][item.item_id.to_string()].is_null())
+        .map(|item| item.item_id)
+        .collect()
+}
@@ -1,10 +1,14 @@
-use super::teams::OpponantTeam;
+use super::{items, stats, teams::OpponantTeam};
 use crate::{active_player, all_players};
 use serde_json::Value;
 
+// Total and bonus resistances of each opponant, in the same order as OpponantTeam. Total
+// resistances are None for champions missing from the loaded Data Dragon.
 pub struct Resistance {
-    pub armor: Vec<f64>,
-    pub magic_resist: Vec<f64>,
+    pub armor: Vec<Option<f64>>,
+    pub bonus_armor: Vec<f64>,
+    pub magic_resist: Vec<Option<f64>>,
+    pub bonus_magic_resist: Vec<f64>,
 }
 
 impl<'a> Resistance {
@@ -12,18 +16,48 @@ impl<'a> Resistance {
         active_player: &'a active_player::Root,
         all_players: &'a all_players::Root,
         ddragon_champions: &'a Value,
+        ddragon_items: &'a Value,
     ) -> Self {
+        // Enemy stat shards and rune stats aren't exposed by the Live Client, so only
+        // items contribute bonus resistances
+        let opponant_team = OpponantTeam::new(active_player, all_players);
+        let bonus_armor = bonus_stat(&opponant_team, ddragon_items, "FlatArmorMod");
+        let bonus_magic_resist = bonus_stat(&opponant_team, ddragon_items, "FlatSpellBlockMod");
         Resistance {
-            armor: Armor::get_scaled_ar(Armor::new(active_player, all_players, ddragon_champions)),
-            magic_resist: MagicResist::get_scaled_mr(MagicResist::new(
-                active_player,
-                all_players,
-                ddragon_champions,
-            )),
+            armor: add(
+                Armor::get_scaled_ar(Armor::new(active_player, all_players, ddragon_champions)),
+                &bonus_armor,
+            ),
+            bonus_armor,
+            magic_resist: add(
+                MagicResist::get_scaled_mr(MagicResist::new(
+                    active_player,
+                    all_players,
+                    ddragon_champions,
+                )),
+                &bonus_magic_resist,
+            ),
+            bonus_magic_resist,
         }
     }
 }
 
+// Returns the item stat of each opponant
+fn bonus_stat(opponant_team: &OpponantTeam, ddragon_items: &Value, stat: &str) -> Vec<f64> {
+    opponant_team
+        .items
+        .iter()
+        .map(|i| items::item_stat(ddragon_items, i, stat))
+        .collect()
+}
+
+fn add(base: Vec<Option<f64>>, bonus: &[f64]) -> Vec<Option<f64>> {
+    base.iter()
+        .zip(bonus)
+        .map(|(b, bonus)| b.map(|b| b + bonus))
+        .collect()
+}
+
 struct Armor<'a> {
     opponant_team: OpponantTeam,
     ddragon_champions: &'a Value,
@@ -40,29 +74,25 @@ impl<'a> Armor<'a> {
             ddragon_champions,
         }
     }
-    fn get_scaled_ar(ar: Armor) -> Vec<f64> {
-        // Set a Vec<f64> for opponant MR values
+    fn get_scaled_ar(ar: Armor) -> Vec<Option<f64>> {
+        // Set a Vec<Option<f64>> for opponant armor values
         let mut sar = Vec::new();
         for i in 0..ar.opponant_team.opponants.len() {
-            let champion_name = &correct_name(&ar.opponant_team.opponants[i].0 as &str);
-            let base_mr = ar.ddragon_champions["data"][champion_name]["stats"]["spellblock"]
-                .as_f64()
-                .unwrap();
-            let mr_per_level = ar.ddragon_champions["data"][champion_name]["stats"]
-                ["spellblockperlevel"]
-                .as_f64()
-                .unwrap();
-            let level = ar.opponant_team.opponants[i].1 as f64;
-            let scaled_ar = base_mr + (mr_per_level * (level - 1.0));
-            sar.push(scaled_ar)
+            let champion_name =
+                &stats::champion_id(ar.ddragon_champions, &ar.opponant_team.opponants[i].0);
+            let level = ar.opponant_team.opponants[i].1;
+            sar.push(stats::scaled_stat(
+                ar.ddragon_champions,
+                champion_name,
+                "armor",
+                level,
+            ))
         }
         sar
     }
 }
 
 pub struct MagicResist<'a> {
-    pub active_player: &'a active_player::Root,
-    pub all_players: &'a all_players::Root,
     pub opponant_team: OpponantTeam,
     pub ddragon_champions: &'a Value,
 }
@@ -74,37 +104,148 @@ impl<'a> MagicResist<'a> {
         ddragon_champions: &'a Value,
     ) -> Self {
         MagicResist {
-            active_player,
-            all_players,
             opponant_team: OpponantTeam::new(active_player, all_players),
             ddragon_champions,
         }
     }
-    pub fn get_scaled_mr(mr: MagicResist) -> Vec<f64> {
-        // Set a Vec<f64> for opponant MR values
+    pub fn get_scaled_mr(mr: MagicResist) -> Vec<Option<f64>> {
+        // Set a Vec<Option<f64>> for opponant MR values
         let mut smr = Vec::new();
         for i in 0..mr.opponant_team.opponants.len() {
-            let champion_name = &correct_name(&mr.opponant_team.opponants[i].0 as &str);
-            let base_mr = mr.ddragon_champions["data"][champion_name]["stats"]["spellblock"]
-                .as_f64()
-                .unwrap();
-            let mr_per_level = mr.ddragon_champions["data"][champion_name]["stats"]
-                ["spellblockperlevel"]
-                .as_f64()
-                .unwrap();
-            let level = mr.opponant_team.opponants[i].1 as f64;
-            let scaled_mr = base_mr + (mr_per_level * (level - 1.0));
-            smr.push(scaled_mr)
+            let champion_name =
+                &stats::champion_id(mr.ddragon_champions, &mr.opponant_team.opponants[i].0);
+            let level = mr.opponant_team.opponants[i].1;
+            smr.push(stats::scaled_stat(
+                mr.ddragon_champions,
+                champion_name,
+                "spellblock",
+                level,
+            ))
         }
         smr
     }
 }
 
-fn correct_name(name: &str) -> String {
-    match name {
-        "ChoGath" => "Chogath".to_string(),
-        "KhaZix" => "Khazix".to_string(),
-        "KaiSa" => "Kaisa".to_string(),
-        _ => name.to_string(),
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::teams;
+    use std::fs;
+
+    fn ddragon_data(file: &str) -> Value {
+        serde_json::from_str(
+            &fs::read_to_string(format!(
+                "./resources/fixtures/ddragon/12.13.1/{}.json",
+                file
+            ))
+            .expect("Failed to read string from file"),
+        )
+        .expect("Failed to deserialize String into JSON Value")
+    }
+
+    // Loads a frame of the sample data in resources
+    fn sample_frame(cycle: usize) -> (active_player::Root, all_players::Root) {
+        let active_player = serde_json::from_str(
+            &fs::read_to_string(format!(
+                "./resources/active_player/active_player_{}.json",
+                cycle
+            ))
+            .expect("Failed to read string from file"),
+        )
+        .expect("Failed to deserialize string to active_player::Root");
+        let all_players = serde_json::from_str(
+            &(String::from("{ \"allPlayers\": ")
+                + &fs::read_to_string(format!(
+                    "./resources/all_players/all_players_{}.json",
+                    cycle
+                ))
+                .expect("Failed to read string from file")
+                + "}"),
+        )
+        .expect("Failed to deserialize string into all_players::Root");
+        (active_player, all_players)
+    }
+
+    #[test]
+    fn armor_and_magic_resist_diverge_in_sample_frames() {
+        let ddragon_champions = ddragon_data("champion");
+        let ddragon_items = ddragon_data("item");
+        let frames = fs::read_dir("./resources/active_player").unwrap().count();
+        for cycle in 0..frames {
+            let (active_player, all_players) = sample_frame(cycle);
+            let opponant_team = OpponantTeam::new(&active_player, &all_players);
+            let resistance = Resistance::new(
+                &active_player,
+                &all_players,
+                &ddragon_champions,
+                &ddragon_items,
+            );
+            // Base values can coincide at level 1, growth per level never does
+            for (i, (name, _)) in opponant_team
+                .opponants
+                .iter()
+                .enumerate()
+                .filter(|(_, (_, level))| *level > 1)
+            {
+                assert_ne!(
+                    resistance.armor[i], resistance.magic_resist[i],
+                    "armor equals magic resist for {} in frame {}",
+                    name, cycle
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn armor_scales_with_armor_growth() {
+        let ddragon_champions = ddragon_data("champion");
+        let ddragon_items = ddragon_data("item");
+        let (active_player, all_players) = sample_frame(0);
+        let opponant_team = OpponantTeam::new(&active_player, &all_players);
+        let resistance = Resistance::new(
+            &active_player,
+            &all_players,
+            &ddragon_champions,
+            &ddragon_items,
+        );
+        for (i, (name, level)) in opponant_team.opponants.iter().enumerate() {
+            let stats =
+                &ddragon_champions["data"][stats::champion_id(&ddragon_champions, name)]["stats"];
+            let armor = stats::stat_at_level(
+                stats["armor"].as_f64().unwrap(),
+                stats["armorperlevel"].as_f64().unwrap(),
+                *level,
+            );
+            assert_eq!(
+                resistance.armor[i].unwrap() - resistance.bonus_armor[i],
+                armor
+            );
+        }
+    }
+
+    #[test]
+    fn champions_missing_from_ddragon_have_no_resistances() {
+        let ddragon_champions = ddragon_data("champion");
+        let ddragon_items = ddragon_data("item");
+        let (active_player, mut all_players) = sample_frame(0);
+        let team = teams::active_all_player(&active_player, &all_players)
+            .team
+            .clone();
+        // The first opponant in OpponantTeam order
+        let missing = all_players
+            .all_players
+            .iter_mut()
+            .find(|p| p.team != team)
+            .unwrap();
+        missing.champion_name = "NotAChampion".to_string();
+        let resistance = Resistance::new(
+            &active_player,
+            &all_players,
+            &ddragon_champions,
+            &ddragon_items,
+        );
+        assert_eq!(resistance.armor[0], None);
+        assert_eq!(resistance.magic_resist[0], None);
+        assert!(resistance.armor[1..].iter().all(Option::is_some));
     }
 }
@@ -0,0 +1,239 @@
+use super::{items, stats::by_level, teams::OpponantTeam};
+use crate::{active_player, all_players, summoners::SummonerSpell};
+use serde_json::Value;
+
+// Shield a champion can put on themselves as (level 1, level 18, AP ratio). Ability ranks of
+// opponants aren't known, so ability shields are scaled from rank 1 to max rank by level.
+fn champion_shield(champion_name: &str) -> Option<(f64, f64, f64)> {
+    match champion_name {
+        // Help, Pix!
+        "Lulu" => Some((80.0, 240.0, 0.4)),
+        // Inspire
+        "Karma" => Some((90.0, 250.0, 0.5)),
+        // Prismatic Barrier
+        "Lux" => Some((45.0, 125.0, 0.35)),
+        // Command: Protect
+        "Orianna" => Some((60.0, 180.0, 0.5)),
+        // Surround Sound
+        "Seraphine" => Some((60.0, 140.0, 0.4)),
+        // Eye of the Storm
+        "Janna" => Some((80.0, 200.0, 0.55)),
+        _ => None,
+    }
+}
+
+// Health Heal and Barrier give their caster as (level 1, level 18). Heal is counted as a
+// shield since it lands before the burst finishes.
+fn summoner_shield(summoner_spell: SummonerSpell) -> Option<(f64, f64)> {
+    match summoner_spell {
+        SummonerSpell::Heal => Some((80.0, 318.0)),
+        SummonerSpell::Barrier => Some((105.0, 411.0)),
+        _ => None,
+    }
+}
+
+// Estimated shields and heals each opponant can use to survive a burst, in the same order
+// as OpponantTeam. Magic shields are kept apart since they only absorb magic damage, and
+// spell shields since they block an ability whatever its damage.
+pub struct Shields {
+    pub shield: Vec<f64>,
+    pub magic_shield: Vec<f64>,
+    pub spell_shield: Vec<bool>,
+}
+
+impl Shields {
+    pub fn new(
+        active_player: &active_player::Root,
+        all_players: &all_players::Root,
+        ddragon_items: &Value,
+    ) -> Self {
+        let opponant_team = OpponantTeam::new(active_player, all_players);
+        let mut shield = Vec::new();
+        let mut magic_shield = Vec::new();
+        let mut spell_shield = Vec::new();
+        for i in 0..opponant_team.opponants.len() {
+            let champion_name = &opponant_team.opponants[i].0;
+            let level = opponant_team.opponants[i].1;
+            let opponant_items = &opponant_team.items[i];
+            let bonus_health = items::item_stat(ddragon_items, opponant_items, "FlatHPPoolMod");
+            let bonus_attack_damage =
+                items::item_stat(ddragon_items, opponant_items, "FlatPhysicalDamageMod");
+            let ability_power =
+                items::item_stat(ddragon_items, opponant_items, "FlatMagicDamageMod");
+
+            let mut total = 0.0;
+            // Lifeline shields don't stack, only the strongest one applies. Whether it only
+            // absorbs magic damage is kept alongside it.
+            let mut lifeline: (f64, bool) = (0.0, false);
+            for item in opponant_items {
+                let candidate = match item.item_id {
+                    // Sterak's Gage
+                    3053 => (0.75 * bonus_health, false),
+                    // Hexdrinker
+                    3155 => (by_level(110.0, 280.0, level), true),
+                    // Maw of Malmortius
+                    3156 => (200.0 + 1.125 * bonus_attack_damage, true),
+                    // Locket of the Iron Solari
+                    3190 => {
+                        total += by_level(200.0, 365.0, level);
+                        continue;
+                    }
+                    _ => continue,
+                };
+                if candidate.0 > lifeline.0 {
+                    lifeline = candidate;
+                }
+            }
+            let magic_total = if lifeline.1 { lifeline.0 } else { 0.0 };
+            if !lifeline.1 {
+                total += lifeline.0;
+            }
+            if let Some((min, max, ap_ratio)) = champion_shield(champion_name) {
+                total += by_level(min, max, level) + ap_ratio * ability_power;
+            }
+            for summoner_spell in opponant_team.summoner_spells[i] {
+                if let Some((min, max)) = summoner_shield(summoner_spell) {
+                    total += by_level(min, max, level);
+                }
+            }
+            debug!(
+                "{} can shield {} and {} magic damage",
+                champion_name, total, magic_total
+            );
+            shield.push(total);
+            magic_shield.push(magic_total);
+            // Banshee's Veil and Edge of Night
+            spell_shield.push(
+                opponant_items
+                    .iter()
+                    .any(|item| matches!(item.item_id, 3102 | 3814)),
+            );
+        }
+        Shields {
+            shield,
+            magic_shield,
+            spell_shield,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dmg;
+    use std::fs;
+
+    const RUBY_CRYSTAL: i64 = 1028;
+    const STERAKS_GAGE: i64 = 3053;
+    const BANSHEES_VEIL: i64 = 3102;
+    const HEXDRINKER: i64 = 3155;
+    const MAW_OF_MALMORTIUS: i64 = 3156;
+
+    const HEAL: &str = "GeneratedTip_SummonerSpell_SummonerHeal_DisplayName";
+    const BARRIER: &str = "GeneratedTip_SummonerSpell_SummonerBarrier_DisplayName";
+    const FLASH: &str = "GeneratedTip_SummonerSpell_SummonerFlash_DisplayName";
+
+    fn ddragon_items() -> Value {
+        serde_json::from_str(
+            &fs::read_to_string("./resources/fixtures/ddragon/12.13.1/item.json")
+                .expect("Failed to read string from file"),
+        )
+        .expect("Failed to deserialize String into JSON Value")
+    }
+
+    // Shields of a level 1 Ashe with the items and summoner spells, facing the active player
+    fn shields(item_ids: &[i64], summoner_spells: [&str; 2]) -> (f64, f64, bool) {
+        let active_player = active_player::Root {
+            summoner_name: "Active".to_string(),
+            ..Default::default()
+        };
+        let mut opponant = all_players::AllPlayer {
+            champion_name: "Ashe".to_string(),
+            summoner_name: "Opponant".to_string(),
+            team: "CHAOS".to_string(),
+            level: 1,
+            items: (0..)
+                .zip(item_ids)
+                .map(|(slot, item_id)| all_players::Item {
+                    item_id: *item_id,
+                    slot,
+                    count: 1,
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        };
+        let spells = &mut opponant.summoner_spells;
+        spells.summoner_spell_one.raw_display_name = summoner_spells[0].to_string();
+        spells.summoner_spell_two.raw_display_name = summoner_spells[1].to_string();
+        let all_players = all_players::Root {
+            all_players: vec![
+                all_players::AllPlayer {
+                    summoner_name: "Active".to_string(),
+                    team: "ORDER".to_string(),
+                    ..Default::default()
+                },
+                opponant,
+            ],
+        };
+        let shields = Shields::new(&active_player, &all_players, &ddragon_items());
+        (
+            shields.shield[0],
+            shields.magic_shield[0],
+            shields.spell_shield[0],
+        )
+    }
+
+    fn target(shield: f64, magic_shield: f64) -> dmg::Target {
+        dmg::Target {
+            resistance: dmg::Resistance::new(0.0, 0.0, 0.0, 0.0),
+            max_health: 1000.0,
+            current_health: 1000.0,
+            shield,
+            magic_shield,
+            spell_shield: false,
+            position: 0,
+        }
+    }
+
+    #[test]
+    fn heal_and_barrier_count_toward_effective_health() {
+        assert_eq!(shields(&[], [FLASH, FLASH]), (0.0, 0.0, false));
+        assert_eq!(shields(&[], [HEAL, FLASH]).0, 80.0);
+        let (shield, magic_shield, _) = shields(&[], [HEAL, BARRIER]);
+        assert_eq!(shield, 185.0);
+        assert_eq!(target(shield, magic_shield).effective_health(), 1185.0);
+    }
+
+    #[test]
+    fn magic_shields_only_absorb_magic_damage() {
+        // Hexdrinker shields 110 at level 1, Maw of Malmortius 200 + 112.5% bonus AD
+        assert_eq!(shields(&[HEXDRINKER], [FLASH, FLASH]), (0.0, 110.0, false));
+        let (shield, magic_shield, _) = shields(&[MAW_OF_MALMORTIUS], [FLASH, FLASH]);
+        assert_eq!((shield, magic_shield), (0.0, 256.25));
+        let target = target(shield, magic_shield);
+        assert_eq!(target.damaged(0.0, 300.0).current_health, 700.0);
+        assert_eq!(target.damaged(300.0, 0.0).current_health, 956.25);
+    }
+
+    #[test]
+    fn banshees_veil_is_a_spell_shield() {
+        assert_eq!(shields(&[BANSHEES_VEIL], [FLASH, FLASH]), (0.0, 0.0, true));
+    }
+
+    #[test]
+    fn lifeline_shields_dont_stack() {
+        // Both magic lifelines only give the stronger Maw of Malmortius shield
+        let both = shields(&[HEXDRINKER, MAW_OF_MALMORTIUS], [FLASH, FLASH]);
+        assert_eq!((both.0, both.1), (0.0, 256.25 + 1.125 * 25.0));
+        // Sterak's Gage shields 75% of the 700 bonus health, beating Maw of Malmortius
+        let (shield, magic_shield, _) = shields(
+            &[STERAKS_GAGE, RUBY_CRYSTAL, RUBY_CRYSTAL, MAW_OF_MALMORTIUS],
+            [FLASH, FLASH],
+        );
+        assert_eq!((shield, magic_shield), (525.0, 0.0));
+        // The rest of the shields still add up with the lifeline
+        let (shield, _, _) = shields(&[STERAKS_GAGE], [BARRIER, FLASH]);
+        assert_eq!(shield, 300.0 + 105.0);
+    }
+}
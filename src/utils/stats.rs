@@ -0,0 +1,88 @@
+use serde_json::Value;
+
+// Returns a champion stat at the given level using the in-game growth curve.
+pub fn stat_at_level(base: f64, per_level: f64, level: i64) -> f64 {
+    let n = (level - 1) as f64;
+    base + per_level * n * (0.7025 + 0.0175 * n)
+}
+
+// Values that scale linearly from level 1 to level 18, like rune damage and item shields
+pub fn by_level(min: f64, max: f64, level: i64) -> f64 {
+    min + (max - min) * (level - 1).clamp(0, 17) as f64 / 17.0
+}
+
+// Returns the Data Dragon stat and its per level growth scaled to the given level, or None
+// for champions missing from the loaded Data Dragon
+pub fn scaled_stat(
+    ddragon_champions: &Value,
+    champion_name: &str,
+    stat: &str,
+    level: i64,
+) -> Option<f64> {
+    let stats = &ddragon_champions["data"][champion_name]["stats"];
+    let base = stats[stat].as_f64()?;
+    let per_level = stats[stat.to_string() + "perlevel"].as_f64()?;
+    Some(stat_at_level(base, per_level, level))
+}
+
+// Maps a champion name from OpponantTeam to its Data Dragon id by matching it against the
+// names in Data Dragon, ignoring case, spaces and punctuation. Ids often differ from names,
+// e.g. MonkeyKing for Wukong or Nunu for Nunu & Willump. Names missing from Data Dragon are
+// returned as is.
+pub fn champion_id(ddragon_champions: &Value, name: &str) -> String {
+    let normalize = |name: &str| -> String {
+        name.chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect()
+    };
+    let normalized = normalize(name);
+    ddragon_champions["data"]
+        .as_object()
+        .and_then(|champions| {
+            champions.iter().find_map(|(id, champion)| {
+                let ddragon_name = champion["name"].as_str()?;
+                (normalize(ddragon_name) == normalized).then(|| id.clone())
+            })
+        })
+        .unwrap_or_else(|| name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn champion_ids_are_found_by_data_dragon_name() {
+        let ddragon_champions = json!({
+            "data": {
+                "Belveth": { "name": "Bel'Veth" },
+                "Chogath": { "name": "Cho'Gath" },
+                "DrMundo": { "name": "Dr. Mundo" },
+                "Leblanc": { "name": "LeBlanc" },
+                "MonkeyKing": { "name": "Wukong" },
+                "Nunu": { "name": "Nunu & Willump" },
+                "Renata": { "name": "Renata Glasc" },
+                "Velkoz": { "name": "Vel'Koz" },
+                "Ashe": { "name": "Ashe" }
+            }
+        });
+        // OpponantTeam strips apostrophes and spaces from the Live Client names
+        for (name, id) in [
+            ("BelVeth", "Belveth"),
+            ("ChoGath", "Chogath"),
+            ("Dr.Mundo", "DrMundo"),
+            ("LeBlanc", "Leblanc"),
+            ("Wukong", "MonkeyKing"),
+            ("Nunu&Willump", "Nunu"),
+            ("RenataGlasc", "Renata"),
+            ("VelKoz", "Velkoz"),
+            ("Ashe", "Ashe"),
+        ] {
+            assert_eq!(champion_id(&ddragon_champions, name), id);
+        }
+        assert_eq!(champion_id(&ddragon_champions, "Nilah"), "Nilah");
+        assert_eq!(champion_id(&serde_json::Value::Null, "Ashe"), "Ashe");
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{active_player, all_players};
+use crate::{active_player, all_players, summoners::SummonerSpell};
 
 // Returns a tuple of the index of the active player in all players and the active players team.
 fn get_team(active_player: &active_player::Root, players: &all_players::Root) -> (usize, String) {
@@ -16,14 +16,39 @@ fn get_team(active_player: &active_player::Root, players: &all_players::Root) ->
     res
 }
 
+// Returns the Live Client champion name of the active player.
+pub fn active_champion_name(
+    active_player: &active_player::Root,
+    players: &all_players::Root,
+) -> String {
+    active_all_player(active_player, players)
+        .champion_name
+        .clone()
+}
+
+// Returns the active player's entry in all players.
+pub fn active_all_player<'a>(
+    active_player: &active_player::Root,
+    players: &'a all_players::Root,
+) -> &'a all_players::AllPlayer {
+    let (i, _) = get_team(active_player, players);
+    &players.all_players[i]
+}
+
 pub struct OpponantTeam {
     pub opponants: Vec<(String, i64)>,
+    // Items of each opponant, in the same order as opponants
+    pub items: Vec<Vec<all_players::Item>>,
+    // Summoner spells of each opponant, in the same order as opponants
+    pub summoner_spells: Vec<[SummonerSpell; 2]>,
 }
 
 impl OpponantTeam {
     pub fn new(active_player: &active_player::Root, players: &all_players::Root) -> Self {
         OpponantTeam {
             opponants: OpponantTeam::build_opponant_team(active_player, players),
+            items: OpponantTeam::build_opponant_items(active_player, players),
+            summoner_spells: OpponantTeam::build_opponant_summoner_spells(active_player, players),
         }
     }
 
@@ -39,12 +64,37 @@ impl OpponantTeam {
                     players.all_players[i]
                         .champion_name
                         .clone()
-                        .replace('\'', "")
-                        .replace(' ', ""),
+                        .replace(['\'', ' '], ""),
                     players.all_players[i].level,
                 ));
             }
         }
         opponant_list
     }
+
+    pub fn build_opponant_items(
+        active_player: &active_player::Root,
+        players: &all_players::Root,
+    ) -> Vec<Vec<all_players::Item>> {
+        let team = get_team(active_player, players).1;
+        players
+            .all_players
+            .iter()
+            .filter(|player| player.team != team)
+            .map(|player| player.items.clone())
+            .collect()
+    }
+
+    pub fn build_opponant_summoner_spells(
+        active_player: &active_player::Root,
+        players: &all_players::Root,
+    ) -> Vec<[SummonerSpell; 2]> {
+        let team = get_team(active_player, players).1;
+        players
+            .all_players
+            .iter()
+            .filter(|player| player.team != team)
+            .map(SummonerSpell::from_player)
+            .collect()
+    }
 }